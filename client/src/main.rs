@@ -1,23 +1,116 @@
 mod map;
 mod net;
 
-use crate::map::{generate_maze_level, MazeLevel, MazeMap, Tile, MAZE_HEIGHT, MAZE_WIDTH};
+use crate::map::{generate_procedural_maze, MazeMap, Tile, MAZE_HEIGHT, MAZE_WIDTH};
 use crate::net::NetworkClient;
-use map::{level_1, level_2, level_3, SpawnPoints};
+use ed25519_dalek::{Signer, SigningKey};
+use map::SpawnPoints;
+use rand::rngs::OsRng;
 use sdl2::{
-    event::Event, keyboard::Keycode, pixels::Color, rect::Rect, render::Canvas, video::Window,
+    controller::{Axis, Button},
+    event::Event,
+    keyboard::Keycode,
+    pixels::Color,
+    rect::Rect,
+    render::Canvas,
+    render::Texture,
+    render::TextureCreator,
+    surface::Surface,
+    video::Window,
 };
-use shared::server::{ClientMessage, ServerMessage};
+use shared::server::{ClientMessage, PlayerId, ServerMessage, PROTOCOL_VERSION};
 use shared::{Position, Rotation};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::io::{self, Write};
+use std::path::Path;
 use std::time::{Duration, Instant};
 
 const SCREEN_WIDTH: u32 = 800;
 const SCREEN_HEIGHT: u32 = 600;
 const MINIMAP_TILE_SIZE: u32 = 10;
+/// How long without a single `ServerMessage` before this client gives up
+/// waiting and declares the server gone, rather than leaving the player
+/// stuck on a `Connecting`/`Lobby`/`Playing` screen that will never update
+/// again. Equal to the server's default heartbeat reap window and five times
+/// `HEARTBEAT_INTERVAL`, so a normal hiccup doesn't trip it.
+const SERVER_TIMEOUT: Duration = Duration::from_secs(10);
+/// How often `ClientMessage::Heartbeat` is sent while connected, independent
+/// of whether `Move` happens to be going out - keeps an idle player's
+/// `last_seen` fresh against the server's own heartbeat reaper.
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(2);
 const FOV: f32 = std::f32::consts::FRAC_PI_4;
 const RAY_DISTANCE: f32 = 10.0;
+/// Distance `fog_factor` scales against. Walls and sprites reach its `0.2`
+/// floor (mostly, never fully, faded into `FOG_COLOR`) at 80% of it. Kept equal to `RAY_DISTANCE` so nothing pops in right at the edge of ray
+/// range; levels can diverge the two to feel hazier or clearer.
+const MAX_VIEW_DISTANCE: f32 = RAY_DISTANCE;
+/// Color distant geometry fades toward - matches the sky fill so the fog
+/// blends into the backdrop instead of dimming to black.
+const FOG_COLOR: Color = Color::RGB(135, 206, 235);
+
+/// Falloff factor for `distance` against `MAX_VIEW_DISTANCE`, clamped so
+/// nothing goes fully black (or fully invisible) even at max range.
+fn fog_factor(distance: f32) -> f32 {
+    (1.0 - (distance / MAX_VIEW_DISTANCE)).clamp(0.2, 1.0)
+}
+
+/// Blends `color` toward `FOG_COLOR` by `factor` (`1.0` = unfogged, `0.2` =
+/// maximally faded).
+fn apply_fog(color: Color, factor: f32) -> Color {
+    let blend = |channel: u8, fog_channel: u8| -> u8 {
+        (channel as f32 * factor + fog_channel as f32 * (1.0 - factor)) as u8
+    };
+    Color::RGB(
+        blend(color.r, FOG_COLOR.r),
+        blend(color.g, FOG_COLOR.g),
+        blend(color.b, FOG_COLOR.b),
+    )
+}
+
+/// Fraction of the stick's travel near center that's ignored, so a
+/// controller that doesn't rest at exactly zero doesn't cause drift.
+const CONTROLLER_DEAD_ZONE: f32 = 0.15;
+const CONTROLLER_MOVE_SPEED: f32 = 3.0;
+const CONTROLLER_TURN_SPEED: f32 = 2.5;
+
+/// Maps a raw `i16` axis reading to `[-1.0, 1.0]`. SDL's negative and
+/// positive ranges aren't symmetric (`-32768..=32767`), so the two signs
+/// are normalized separately to avoid a value that can't reach -1.0/1.0.
+fn normalize_axis(value: i16) -> f32 {
+    if value < 0 {
+        value as f32 / 32768.0
+    } else {
+        value as f32 / 32767.0
+    }
+}
+
+/// Zeroes out axis values within `CONTROLLER_DEAD_ZONE` of center.
+fn apply_dead_zone(value: f32) -> f32 {
+    if value.abs() < CONTROLLER_DEAD_ZONE {
+        0.0
+    } else {
+        value
+    }
+}
+
+/// Wall texture bitmaps, indexed by `Tile::Wall`'s texture id. Missing files
+/// just leave that slot unloaded - `render_first_person_view` falls back to
+/// a flat shaded color for any texture id that didn't load.
+const WALL_TEXTURE_PATHS: &[&str] = &["assets/textures/wall0.bmp", "assets/textures/wall1.bmp"];
+
+/// Loads `WALL_TEXTURE_PATHS` into textures indexed the same way, leaving a
+/// `None` slot (rather than failing startup) for any bitmap that's missing
+/// or fails to load.
+fn load_wall_textures<T>(texture_creator: &TextureCreator<T>) -> Vec<Option<Texture<'_>>> {
+    WALL_TEXTURE_PATHS
+        .iter()
+        .map(|path| {
+            Surface::load_bmp(Path::new(path))
+                .ok()
+                .and_then(|surface| texture_creator.create_texture_from_surface(&surface).ok())
+        })
+        .collect()
+}
 
 #[derive(Debug, Clone, Copy)]
 struct Player3D {
@@ -26,6 +119,147 @@ struct Player3D {
     angle: f32,
 }
 
+/// Single source of truth for what screen is showing and which inputs are
+/// accepted, replacing a tangle of `game_started`/`player_dead`/`game_over`
+/// bools that every input arm had to repeat. `Lobby` covers both "waiting
+/// for players" and "waiting for a spawn after `GameStart`" - the roster it
+/// carries is whatever `PlayersInLobby` last reported, and it's also where
+/// spawn assignment happens on the way to `Playing`.
+#[derive(Debug, Clone, PartialEq)]
+enum GameState {
+    Connecting,
+    Lobby { players: Vec<String> },
+    Playing,
+    /// Entered only from `Playing` via Escape; movement/shooting input is
+    /// ignored and an overlay is drawn over the last rendered frame. Escape
+    /// returns to `Playing`, so there's nothing to restore - it's always
+    /// exactly the state this came from.
+    Paused,
+    /// Entered from `Paused` via `O`; Escape returns to `Paused`, same as
+    /// `Paused` itself returns to whatever it came from.
+    Settings,
+    Dead,
+    GameOver { winner: String },
+    /// Entered on a fatal server message or on the client's own liveness
+    /// timeout (no message received for `SERVER_TIMEOUT`), instead of those
+    /// cases silently exiting or leaving the player stuck on whatever screen
+    /// they were on. A keypress is required to actually quit, so the message
+    /// is never missed.
+    Disconnected { message: String },
+}
+
+/// How far behind the latest received snapshot the rendered position of a
+/// remote player sits. Rendering slightly in the past (rather than snapping
+/// straight to each `PlayerMove`) means there are always two real samples to
+/// interpolate between, smoothing over the network's send interval instead
+/// of visibly teleporting a remote player once per server tick.
+const INTERPOLATION_DELAY: Duration = Duration::from_millis(100);
+
+/// How old a buffered snapshot can get before `RemotePlayer::push` drops it
+/// on the next fresh arrival. Nothing is extrapolated: once `render_time`
+/// passes the newest sample, a remote player holds at that sample.
+const SNAPSHOT_MAX_AGE: Duration = Duration::from_millis(500);
+
+/// A remote player's recent position/rotation history, buffered so
+/// `interpolated_players` can render them `INTERPOLATION_DELAY` behind the
+/// latest sample instead of snapping instantly to each `PlayerMove`.
+struct RemotePlayer {
+    username: String,
+    /// Oldest-first. Trimmed in `push` so it never grows past what
+    /// `interpolated` could possibly need.
+    samples: VecDeque<(Instant, Position, Rotation)>,
+}
+
+impl RemotePlayer {
+    fn new(username: String, position: Position, rotation: Rotation) -> Self {
+        let mut samples = VecDeque::new();
+        samples.push_back((Instant::now(), position, rotation));
+        RemotePlayer { username, samples }
+    }
+
+    /// Records a freshly received `PlayerMove`, dropping samples older than
+    /// `SNAPSHOT_MAX_AGE`. After a gap longer than that, the sample before
+    /// the gap is dropped too, so `interpolated` holds at the new one.
+    fn push(&mut self, position: Position, rotation: Rotation) {
+        let now = Instant::now();
+        self.samples.push_back((now, position, rotation));
+        while self
+            .samples
+            .front()
+            .is_some_and(|(t, ..)| now.duration_since(*t) > SNAPSHOT_MAX_AGE)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Position/rotation to render right now: linearly interpolated between
+    /// the two buffered samples that straddle `render_time`, or the nearest
+    /// single sample if `render_time` falls outside the buffered range.
+    fn interpolated(&self, render_time: Instant) -> (Position, Rotation) {
+        let (before, after) = {
+            let mut before = None;
+            let mut after = None;
+            for sample in &self.samples {
+                if sample.0 <= render_time {
+                    before = Some(sample);
+                } else if after.is_none() {
+                    after = Some(sample);
+                }
+            }
+            (before, after)
+        };
+
+        match (before, after) {
+            (Some(before), Some(after)) => {
+                let span = after.0.duration_since(before.0).as_secs_f32();
+                let t = if span > 0.0 {
+                    render_time.duration_since(before.0).as_secs_f32() / span
+                } else {
+                    0.0
+                };
+                (
+                    lerp_position(&before.1, &after.1, t),
+                    lerp_rotation(&before.2, &after.2, t),
+                )
+            }
+            (Some(sample), None) | (None, Some(sample)) => (sample.1, sample.2),
+            (None, None) => (Position::default(), Rotation::default()),
+        }
+    }
+}
+
+fn lerp_position(a: &Position, b: &Position, t: f32) -> Position {
+    Position::new(
+        a.x + (b.x - a.x) * t,
+        a.y + (b.y - a.y) * t,
+        a.z + (b.z - a.z) * t,
+    )
+}
+
+fn lerp_rotation(a: &Rotation, b: &Rotation, t: f32) -> Rotation {
+    Rotation::new(
+        a.pitch + (b.pitch - a.pitch) * t,
+        a.yaw + (b.yaw - a.yaw) * t,
+        a.roll + (b.roll - a.roll) * t,
+    )
+}
+
+/// Snapshot of every buffered remote player's interpolated state, ready to
+/// hand unchanged to `render_first_person_view`/`render_minimap_below`/
+/// `render_radar_hud`, none of which need to know buffering happens at all.
+fn interpolated_players(
+    remote_players: &HashMap<PlayerId, RemotePlayer>,
+) -> HashMap<PlayerId, (String, Position, Rotation)> {
+    let render_time = Instant::now() - INTERPOLATION_DELAY;
+    remote_players
+        .iter()
+        .map(|(&id, remote)| {
+            let (position, rotation) = remote.interpolated(render_time);
+            (id, (remote.username.clone(), position, rotation))
+        })
+        .collect()
+}
+
 fn prompt(text: &str) -> String {
     print!("{}", text);
     io::stdout().flush().unwrap();
@@ -34,59 +268,81 @@ fn prompt(text: &str) -> String {
     buf.trim().to_string()
 }
 
-fn has_line_of_sight(
-    maze: &[[Tile; MAZE_WIDTH]; MAZE_HEIGHT],
-    from: (f32, f32),
-    to: (f32, f32),
-) -> bool {
-    let dx = to.0 - from.0;
-    let dy = to.1 - from.1;
-    let distance = (dx * dx + dy * dy).sqrt();
-
-    let steps = (distance / 0.05).ceil() as usize;
-    for i in 0..steps {
-        let t = i as f32 / steps as f32;
-        let x = from.0 + dx * t;
-        let y = from.1 + dy * t;
-
-        let gx = x as usize;
-        let gy = y as usize;
-
-        if gx >= MAZE_WIDTH || gy >= MAZE_HEIGHT {
-            return false;
-        }
+/// Casts a ray from `player` at `angle` using a DDA grid traversal: instead of
+/// stepping in small fixed increments (slow, and imprecise enough to tunnel
+/// through thin walls), it jumps cell-by-cell along whichever axis reaches
+/// its next grid line first. Returns the perpendicular (not euclidean) wall
+/// distance - which avoids fisheye distortion when used for wall height -
+/// `side` (`0` for an x-facing wall, `1` for a y-facing one, so the caller
+/// can shade the two differently for a depth cue), and the hit wall's
+/// texture id.
+fn cast_ray(maze: &MazeMap, player: &Player3D, angle: f32) -> Option<(f32, u8, u8)> {
+    // The DDA walk below runs in cell space (one unit per tile) so it's
+    // independent of `tile_size`; the hit distance is scaled back to world
+    // units before it's returned.
+    let tile_size = maze.tile_size;
+    let player_x = player.x / tile_size;
+    let player_y = player.y / tile_size;
 
-        if maze[gy][gx] == Tile::Wall {
-            return false;
-        }
-    }
+    let dir_x = angle.cos();
+    let dir_y = angle.sin();
 
-    true
-}
+    let mut cell_x = player_x as i32;
+    let mut cell_y = player_y as i32;
 
-fn cast_ray(
-    maze: &[[Tile; MAZE_WIDTH]; MAZE_HEIGHT],
-    player: &Player3D,
-    angle: f32,
-) -> Option<(f32, f32)> {
-    let mut x = player.x;
-    let mut y = player.y;
-    let dx = angle.cos();
-    let dy = angle.sin();
+    let delta_dist_x = if dir_x == 0.0 {
+        f32::INFINITY
+    } else {
+        (1.0 / dir_x).abs()
+    };
+    let delta_dist_y = if dir_y == 0.0 {
+        f32::INFINITY
+    } else {
+        (1.0 / dir_y).abs()
+    };
 
-    for _ in 0..(RAY_DISTANCE * 10.0) as usize {
-        x += dx * 0.1;
-        y += dy * 0.1;
+    let (step_x, mut side_dist_x) = if dir_x < 0.0 {
+        (-1, (player_x - cell_x as f32) * delta_dist_x)
+    } else {
+        (1, (cell_x as f32 + 1.0 - player_x) * delta_dist_x)
+    };
+    let (step_y, mut side_dist_y) = if dir_y < 0.0 {
+        (-1, (player_y - cell_y as f32) * delta_dist_y)
+    } else {
+        (1, (cell_y as f32 + 1.0 - player_y) * delta_dist_y)
+    };
 
-        let grid_x = x as usize;
-        let grid_y = y as usize;
+    let max_cell_dist = RAY_DISTANCE / tile_size;
+    let max_steps = (max_cell_dist * maze.width().max(maze.height()) as f32) as usize;
+    for _ in 0..max_steps {
+        let side = if side_dist_x < side_dist_y {
+            side_dist_x += delta_dist_x;
+            cell_x += step_x;
+            0
+        } else {
+            side_dist_y += delta_dist_y;
+            cell_y += step_y;
+            1
+        };
 
-        if grid_x >= MAZE_WIDTH || grid_y >= MAZE_HEIGHT {
+        if cell_x < 0
+            || cell_y < 0
+            || cell_x as usize >= maze.width()
+            || cell_y as usize >= maze.height()
+        {
             return None;
         }
 
-        if maze[grid_y][grid_x] == Tile::Wall {
-            return Some((x, y));
+        if let Tile::Wall(tex_id) = maze[cell_y as usize][cell_x as usize] {
+            let perp_dist = if side == 0 {
+                side_dist_x - delta_dist_x
+            } else {
+                side_dist_y - delta_dist_y
+            };
+            if perp_dist > max_cell_dist {
+                return None;
+            }
+            return Some((perp_dist * tile_size, side, tex_id));
         }
     }
     None
@@ -94,9 +350,10 @@ fn cast_ray(
 
 fn render_first_person_view(
     canvas: &mut Canvas<Window>,
-    maze: &[[Tile; MAZE_WIDTH]; MAZE_HEIGHT],
+    maze: &MazeMap,
     player: &Player3D,
-    other_players: &HashMap<String, (Position, Rotation)>,
+    other_players: &HashMap<PlayerId, (String, Position, Rotation)>,
+    wall_textures: &mut [Option<Texture>],
 ) {
     canvas.set_draw_color(Color::RGB(135, 206, 235));
     canvas
@@ -113,60 +370,100 @@ fn render_first_person_view(
         ))
         .unwrap();
 
+    // Perpendicular wall distance per column, so the sprite pass below can
+    // clip enemies against whatever wall is nearer in that column instead of
+    // an all-or-nothing line-of-sight test.
+    let mut z_buffer = [f32::INFINITY; SCREEN_WIDTH as usize];
+
     for x in 0..SCREEN_WIDTH {
         let ray_angle = player.angle - FOV / 2.0 + (x as f32 / SCREEN_WIDTH as f32) * FOV;
 
-        if let Some((hit_x, hit_y)) = cast_ray(maze, player, ray_angle) {
-            let distance = ((hit_x - player.x).powi(2) + (hit_y - player.y).powi(2)).sqrt();
+        if let Some((distance, side, tex_id)) = cast_ray(maze, player, ray_angle) {
+            z_buffer[x as usize] = distance;
             let wall_height = (SCREEN_HEIGHT as f32 / distance).min(SCREEN_HEIGHT as f32);
             let wall_top = (SCREEN_HEIGHT as f32 - wall_height) / 2.0;
+            let dst_rect = Rect::new(x as i32, wall_top as i32, 1, wall_height as u32);
+
+            let fog = apply_fog(Color::RGB(255, 255, 255), fog_factor(distance));
+
+            let texture = wall_textures.get_mut(tex_id as usize).and_then(|t| t.as_mut());
+            if let Some(texture) = texture {
+                // Exact world-space point the ray hit the wall face; its
+                // fractional part selects which texture column to sample so
+                // the texture doesn't repeat identically across a whole wall.
+                let dir_x = ray_angle.cos();
+                let dir_y = ray_angle.sin();
+                let wall_x = if side == 0 {
+                    player.y + distance * dir_y
+                } else {
+                    player.x + distance * dir_x
+                };
+                let wall_x_cell = wall_x / maze.tile_size;
+                let wall_x_frac = wall_x_cell - wall_x_cell.floor();
 
-            canvas.set_draw_color(Color::RGB(100, 100, 100));
-            canvas
-                .fill_rect(Rect::new(x as i32, wall_top as i32, 1, wall_height as u32))
-                .unwrap();
+                let tex_width = texture.query().width;
+                let tex_x = (wall_x_frac * tex_width as f32) as i32;
+                let src_rect = Rect::new(tex_x.min(tex_width as i32 - 1), 0, 1, texture.query().height);
+                texture.set_color_mod(fog.r, fog.g, fog.b);
+                let _ = canvas.copy(texture, Some(src_rect), Some(dst_rect));
+            } else {
+                // No texture loaded for this wall - fall back to a flat
+                // shade, darkening y-facing walls slightly so adjacent
+                // x/y-facing surfaces read as distinct faces.
+                let shade = if side == 1 { 70 } else { 100 };
+                canvas.set_draw_color(apply_fog(Color::RGB(shade, shade, shade), fog_factor(distance)));
+                canvas.fill_rect(dst_rect).unwrap();
+            }
         }
     }
 
-    canvas.set_draw_color(Color::RGB(0, 0, 255));
-    for (_id, (pos, _rot)) in other_players.iter() {
-        let dx = pos.x - player.x;
-        let dy = pos.y - player.y;
-        let distance = (dx * dx + dy * dy).sqrt();
-        if distance > RAY_DISTANCE {
-            continue;
-        }
+    // Camera-space sprite projection (the inverse of the [dirX dirY; planeX
+    // planeY] camera matrix), so each enemy stripe can be depth-tested
+    // against `z_buffer` for correct partial occlusion instead of an
+    // all-or-nothing line-of-sight check. Sprites are drawn far-to-near so
+    // closer ones correctly overdraw farther ones.
+    let dir_x = player.angle.cos();
+    let dir_y = player.angle.sin();
+    let plane_len = (FOV / 2.0).tan();
+    let plane_x = -dir_y * plane_len;
+    let plane_y = dir_x * plane_len;
 
-        if !has_line_of_sight(maze, (player.x, player.y), (pos.x, pos.y)) {
-            continue;
-        }
+    let mut sorted_players: Vec<_> = other_players.values().collect();
+    sorted_players.sort_by(|a, b| {
+        let dist_sq = |pos: &Position| (pos.x - player.x).powi(2) + (pos.y - player.y).powi(2);
+        dist_sq(&b.1).partial_cmp(&dist_sq(&a.1)).unwrap()
+    });
 
-        let angle_to_enemy = dy.atan2(dx);
-        let mut angle_diff = angle_to_enemy - player.angle;
-        while angle_diff > std::f32::consts::PI {
-            angle_diff -= 2.0 * std::f32::consts::PI;
-        }
-        while angle_diff < -std::f32::consts::PI {
-            angle_diff += 2.0 * std::f32::consts::PI;
-        }
+    for (_username, pos, _rot) in sorted_players {
+        let sprite_x = pos.x - player.x;
+        let sprite_y = pos.y - player.y;
+
+        let inv_det = 1.0 / (plane_x * dir_y - dir_x * plane_y);
+        let transform_x = inv_det * (dir_y * sprite_x - dir_x * sprite_y);
+        let transform_y = inv_det * (-plane_y * sprite_x + plane_x * sprite_y);
 
-        if angle_diff.abs() > FOV / 2.0 {
+        if transform_y <= 0.0 || transform_y > RAY_DISTANCE {
             continue;
         }
 
-        let screen_x = ((angle_diff + FOV / 2.0) / FOV) * SCREEN_WIDTH as f32;
-        let sprite_height = (SCREEN_HEIGHT as f32 / distance).min(SCREEN_HEIGHT as f32 / 1.5);
-        let sprite_width = sprite_height / 2.0;
-        let top = (SCREEN_HEIGHT as f32 - sprite_height) / 2.0;
+        canvas.set_draw_color(apply_fog(Color::RGB(0, 0, 255), fog_factor(transform_y)));
 
-        let rect = Rect::new(
-            (screen_x - sprite_width / 2.0) as i32,
-            top as i32,
-            sprite_width as u32,
-            sprite_height as u32,
-        );
+        let sprite_screen_x =
+            (SCREEN_WIDTH as f32 / 2.0) * (1.0 + transform_x / transform_y);
+        let sprite_height =
+            (SCREEN_HEIGHT as f32 / transform_y).min(SCREEN_HEIGHT as f32 / 1.5) as i32;
+        let sprite_width = sprite_height / 2;
+        let draw_top = ((SCREEN_HEIGHT as i32 - sprite_height) / 2).max(0);
+
+        let draw_start_x = ((sprite_screen_x as i32) - sprite_width / 2).max(0);
+        let draw_end_x =
+            ((sprite_screen_x as i32) + sprite_width / 2).min(SCREEN_WIDTH as i32 - 1);
 
-        let _ = canvas.fill_rect(rect);
+        for stripe in draw_start_x..draw_end_x {
+            if transform_y < z_buffer[stripe as usize] {
+                let _ = canvas.fill_rect(Rect::new(stripe, draw_top, 1, sprite_height as u32));
+            }
+        }
     }
 }
 
@@ -181,21 +478,104 @@ fn render_health_bar(canvas: &mut Canvas<Window>, health: u32) {
     let _ = canvas.fill_rect(Rect::new(20, 20, health_width, height));
 }
 
+/// Small text readout of the locally equipped weapon slot and its remaining
+/// ammo, kept in sync by `ServerMessage::WeaponSwitch`/`ReloadComplete` for
+/// our own `player_id` - there's no message that reports the starting
+/// loadout up front, so this reads "?" until the first of either arrives.
+fn render_ammo_hud<T>(
+    canvas: &mut Canvas<Window>,
+    texture_creator: &TextureCreator<T>,
+    small_font: &sdl2::ttf::Font<'_, '_>,
+    equipped_index: Option<u8>,
+    ammo_count: Option<u32>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let slot = equipped_index.map(|i| (i + 1).to_string()).unwrap_or_else(|| "?".to_string());
+    let ammo = ammo_count.map(|a| a.to_string()).unwrap_or_else(|| "?".to_string());
+    let text = format!("Weapon {}  |  Ammo {}  [1-3 switch, R reload]", slot, ammo);
+    let surface = small_font.render(&text).blended(Color::RGB(230, 230, 230))?;
+    let texture = texture_creator.create_texture_from_surface(&surface)?;
+    let width = surface.width();
+    let rect = Rect::new(20, 50, width, 24);
+    canvas.copy(&texture, None, Some(rect))?;
+    Ok(())
+}
+
+/// World units of radius the radar covers - a remote player further than
+/// this from the local one doesn't get a blip at all.
+const RADAR_RANGE: f32 = 12.0;
+const RADAR_SCREEN_RADIUS: i32 = 70;
+const RADAR_CENTER_X: i32 = SCREEN_WIDTH as i32 - RADAR_SCREEN_RADIUS - 20;
+const RADAR_CENTER_Y: i32 = RADAR_SCREEN_RADIUS + 20;
+
+/// Circular heading-relative radar, separate from the top-down minimap:
+/// every known remote player is projected into the local player's own
+/// reference frame (rotated by `-player.angle`, so "up" on the radar is
+/// always "forward" for the viewer, not north), clipped to `RADAR_RANGE`,
+/// and drawn as a blip. Useful in a maze where line-of-sight rarely shows
+/// an approaching player before they're already close.
+fn render_radar_hud(
+    canvas: &mut Canvas<Window>,
+    player: &Player3D,
+    other_players: &HashMap<PlayerId, (String, Position, Rotation)>,
+) {
+    canvas.set_draw_color(Color::RGBA(20, 20, 20, 200));
+    canvas.set_blend_mode(sdl2::render::BlendMode::Blend);
+    let _ = canvas.fill_rect(Rect::new(
+        RADAR_CENTER_X - RADAR_SCREEN_RADIUS,
+        RADAR_CENTER_Y - RADAR_SCREEN_RADIUS,
+        RADAR_SCREEN_RADIUS as u32 * 2,
+        RADAR_SCREEN_RADIUS as u32 * 2,
+    ));
+    canvas.set_blend_mode(sdl2::render::BlendMode::None);
+
+    // The local player is always the center, facing "up".
+    canvas.set_draw_color(Color::RGB(0, 255, 0));
+    let _ = canvas.fill_rect(Rect::new(RADAR_CENTER_X - 2, RADAR_CENTER_Y - 2, 4, 4));
+
+    let cos_a = (-player.angle).cos();
+    let sin_a = (-player.angle).sin();
+
+    for (_username, pos, _rot) in other_players.values() {
+        let dx = pos.x - player.x;
+        let dy = pos.y - player.y;
+        let distance = (dx * dx + dy * dy).sqrt();
+        if distance > RADAR_RANGE {
+            continue;
+        }
+
+        // Rotate into the player's own frame, then swap so "forward"
+        // (the player's `angle`) points up on screen instead of right.
+        let local_x = dx * cos_a - dy * sin_a;
+        let local_y = dx * sin_a + dy * cos_a;
+
+        let scale = RADAR_SCREEN_RADIUS as f32 / RADAR_RANGE;
+        let blip_x = RADAR_CENTER_X + (local_y * scale) as i32;
+        let blip_y = RADAR_CENTER_Y - (local_x * scale) as i32;
+
+        canvas.set_draw_color(Color::RGB(220, 50, 50));
+        let _ = canvas.fill_rect(Rect::new(blip_x - 3, blip_y - 3, 6, 6));
+    }
+}
+
 fn render_minimap_below(
     canvas: &mut Canvas<Window>,
-    maze: &[[Tile; MAZE_WIDTH]; MAZE_HEIGHT],
+    maze: &MazeMap,
     player: &Player3D,
-    other_players: &HashMap<String, (Position, Rotation)>,
+    other_players: &HashMap<PlayerId, (String, Position, Rotation)>,
 ) {
-    let minimap_width = (MAZE_WIDTH * MINIMAP_TILE_SIZE as usize) as u32;
-    let minimap_height = (MAZE_HEIGHT * MINIMAP_TILE_SIZE as usize) as u32;
+    let minimap_width = (maze.width() * MINIMAP_TILE_SIZE as usize) as u32;
+    let minimap_height = (maze.height() * MINIMAP_TILE_SIZE as usize) as u32;
     let offset_x = ((SCREEN_WIDTH - minimap_width) / 2) as i32;
     let offset_y = (SCREEN_HEIGHT - minimap_height) as i32 - 10;
 
-    for (y, row) in maze.iter().enumerate() {
+    // World-space position, in tiles, so the marker lands on the right
+    // minimap cell regardless of how large a world unit one tile is.
+    let to_tile = |world: f32| -> usize { (world / maze.tile_size) as usize };
+
+    for (y, row) in maze.rows().enumerate() {
         for (x, tile) in row.iter().enumerate() {
             let color = match tile {
-                Tile::Wall => Color::RGB(80, 80, 80),
+                Tile::Wall(_) => Color::RGB(80, 80, 80),
                 Tile::Floor => Color::RGB(200, 200, 200),
             };
 
@@ -211,56 +591,237 @@ fn render_minimap_below(
 
     canvas.set_draw_color(Color::RGB(255, 0, 0));
     let _ = canvas.fill_rect(Rect::new(
-        offset_x + (player.x as usize * MINIMAP_TILE_SIZE as usize) as i32,
-        offset_y + (player.y as usize * MINIMAP_TILE_SIZE as usize) as i32,
+        offset_x + (to_tile(player.x) * MINIMAP_TILE_SIZE as usize) as i32,
+        offset_y + (to_tile(player.y) * MINIMAP_TILE_SIZE as usize) as i32,
         MINIMAP_TILE_SIZE,
         MINIMAP_TILE_SIZE,
     ));
 
-    canvas.set_draw_color(Color::RGB(0, 0, 255));
-    for (_name, (pos, _rot)) in other_players.iter() {
+    for (_id, (_username, pos, _rot)) in other_players.iter() {
+        let dx = pos.x - player.x;
+        let dy = pos.y - player.y;
+        let distance = (dx * dx + dy * dy).sqrt();
+        canvas.set_draw_color(apply_fog(Color::RGB(0, 0, 255), fog_factor(distance)));
         let _ = canvas.fill_rect(Rect::new(
-            offset_x + (pos.x as usize * MINIMAP_TILE_SIZE as usize) as i32,
-            offset_y + (pos.y as usize * MINIMAP_TILE_SIZE as usize) as i32,
+            offset_x + (to_tile(pos.x) * MINIMAP_TILE_SIZE as usize) as i32,
+            offset_y + (to_tile(pos.y) * MINIMAP_TILE_SIZE as usize) as i32,
             MINIMAP_TILE_SIZE,
             MINIMAP_TILE_SIZE,
         ));
     }
 }
 
-fn find_target_in_crosshair(
-    player: &Player3D,
-    others: &HashMap<String, (Position, Rotation)>,
-) -> Option<(String, Position)> {
-    let mut best_target: Option<(String, Position)> = None;
-    let mut closest_angle = std::f32::consts::PI;
+/// Waiting-room screen shown for `GameState::Connecting`/`Lobby`: a title and
+/// the roster `PlayersInLobby` last reported, with the local player flagged.
+fn render_lobby_screen<T>(
+    canvas: &mut Canvas<Window>,
+    texture_creator: &TextureCreator<T>,
+    font: &sdl2::ttf::Font<'_, '_>,
+    small_font: &sdl2::ttf::Font<'_, '_>,
+    players: &[String],
+    username: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let title_surface = font
+        .render("Waiting for Players...")
+        .blended(Color::RGB(255, 255, 255))?;
+    let title_texture = texture_creator.create_texture_from_surface(&title_surface)?;
+    let title_width = title_surface.width();
+    let title_rect = Rect::new(
+        (SCREEN_WIDTH as i32 - title_width as i32) / 2,
+        80,
+        title_width,
+        60,
+    );
+    canvas.copy(&title_texture, None, Some(title_rect))?;
 
-    for (name, (pos, _rot)) in others.iter() {
-        let dx = pos.x - player.x;
-        let dy = pos.y - player.y;
-        let distance = (dx * dx + dy * dy).sqrt();
-        if distance > 30.0 {
-            continue;
-        }
+    for (i, name) in players.iter().enumerate() {
+        let label = if name == username {
+            format!("{} (you)", name)
+        } else {
+            name.clone()
+        };
+        let surface = small_font.render(&label).blended(Color::RGB(200, 200, 200))?;
+        let texture = texture_creator.create_texture_from_surface(&surface)?;
+        let width = surface.width();
+        let rect = Rect::new(
+            (SCREEN_WIDTH as i32 - width as i32) / 2,
+            180 + i as i32 * 40,
+            width,
+            32,
+        );
+        canvas.copy(&texture, None, Some(rect))?;
+    }
 
-        let angle_to = dy.atan2(dx);
-        let mut angle_diff = angle_to - player.angle;
+    Ok(())
+}
 
-        while angle_diff > std::f32::consts::PI {
-            angle_diff -= 2.0 * std::f32::consts::PI;
-        }
-        while angle_diff < -std::f32::consts::PI {
-            angle_diff += 2.0 * std::f32::consts::PI;
-        }
+/// Modal dialog shown on a fatal server error or a liveness timeout, so the
+/// player always sees *why* the connection ended instead of the process
+/// silently exiting or the last screen just freezing forever.
+fn render_disconnect_dialog<T>(
+    canvas: &mut Canvas<Window>,
+    texture_creator: &TextureCreator<T>,
+    font: &sdl2::ttf::Font<'_, '_>,
+    small_font: &sdl2::ttf::Font<'_, '_>,
+    message: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let title_surface = font.render("Disconnected").blended(Color::RGB(255, 80, 80))?;
+    let title_texture = texture_creator.create_texture_from_surface(&title_surface)?;
+    let title_width = title_surface.width();
+    canvas.copy(
+        &title_texture,
+        None,
+        Some(Rect::new(
+            (SCREEN_WIDTH as i32 - title_width as i32) / 2,
+            220,
+            title_width,
+            60,
+        )),
+    )?;
 
-        if angle_diff.abs() < 0.2 && angle_diff.abs() < closest_angle {
-            closest_angle = angle_diff.abs();
-            best_target = Some((name.clone(), *pos));
-        }
-    }
+    let message_surface = small_font.render(message).blended(Color::RGB(220, 220, 220))?;
+    let message_texture = texture_creator.create_texture_from_surface(&message_surface)?;
+    let message_width = message_surface.width();
+    canvas.copy(
+        &message_texture,
+        None,
+        Some(Rect::new(
+            (SCREEN_WIDTH as i32 - message_width as i32) / 2,
+            300,
+            message_width,
+            32,
+        )),
+    )?;
+
+    let hint_surface = small_font
+        .render("Press any key to exit")
+        .blended(Color::RGB(160, 160, 160))?;
+    let hint_texture = texture_creator.create_texture_from_surface(&hint_surface)?;
+    let hint_width = hint_surface.width();
+    canvas.copy(
+        &hint_texture,
+        None,
+        Some(Rect::new(
+            (SCREEN_WIDTH as i32 - hint_width as i32) / 2,
+            360,
+            hint_width,
+            32,
+        )),
+    )?;
 
-    best_target
+    Ok(())
 }
+
+/// Dimming overlay drawn over whatever `Playing` last rendered, so pausing
+/// doesn't lose the player's orientation the way cutting to a blank menu
+/// screen would.
+fn render_pause_overlay<T>(
+    canvas: &mut Canvas<Window>,
+    texture_creator: &TextureCreator<T>,
+    font: &sdl2::ttf::Font<'_, '_>,
+    small_font: &sdl2::ttf::Font<'_, '_>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    canvas.set_blend_mode(sdl2::render::BlendMode::Blend);
+    canvas.set_draw_color(Color::RGBA(0, 0, 0, 160));
+    canvas.fill_rect(Rect::new(0, 0, SCREEN_WIDTH, SCREEN_HEIGHT))?;
+    canvas.set_blend_mode(sdl2::render::BlendMode::None);
+
+    let title_surface = font.render("PAUSED").blended(Color::RGB(255, 255, 255))?;
+    let title_texture = texture_creator.create_texture_from_surface(&title_surface)?;
+    let title_width = title_surface.width();
+    canvas.copy(
+        &title_texture,
+        None,
+        Some(Rect::new(
+            (SCREEN_WIDTH as i32 - title_width as i32) / 2,
+            200,
+            title_width,
+            60,
+        )),
+    )?;
+
+    let hint_surface = small_font
+        .render("ESC to resume  |  O for settings  |  Q to quit")
+        .blended(Color::RGB(200, 200, 200))?;
+    let hint_texture = texture_creator.create_texture_from_surface(&hint_surface)?;
+    let hint_width = hint_surface.width();
+    canvas.copy(
+        &hint_texture,
+        None,
+        Some(Rect::new(
+            (SCREEN_WIDTH as i32 - hint_width as i32) / 2,
+            280,
+            hint_width,
+            32,
+        )),
+    )?;
+
+    Ok(())
+}
+
+/// Settings screen reachable from `Paused`. This client has no audio system
+/// and renders at a single fixed resolution, so the two knobs a real game
+/// menu would expose here - volume and display quality - don't have anything
+/// to attach to; turn sensitivity is the one setting this client actually
+/// has, and it's the one exposed here rather than faking the others.
+fn render_settings_screen<T>(
+    canvas: &mut Canvas<Window>,
+    texture_creator: &TextureCreator<T>,
+    font: &sdl2::ttf::Font<'_, '_>,
+    small_font: &sdl2::ttf::Font<'_, '_>,
+    turn_sensitivity: f32,
+) -> Result<(), Box<dyn std::error::Error>> {
+    canvas.set_draw_color(Color::RGB(20, 20, 20));
+    canvas.clear();
+
+    let title_surface = font.render("SETTINGS").blended(Color::RGB(255, 255, 255))?;
+    let title_texture = texture_creator.create_texture_from_surface(&title_surface)?;
+    let title_width = title_surface.width();
+    canvas.copy(
+        &title_texture,
+        None,
+        Some(Rect::new(
+            (SCREEN_WIDTH as i32 - title_width as i32) / 2,
+            160,
+            title_width,
+            60,
+        )),
+    )?;
+
+    let value_text = format!("Turn sensitivity: {:.2}x", turn_sensitivity);
+    let value_surface = small_font.render(&value_text).blended(Color::RGB(230, 230, 230))?;
+    let value_texture = texture_creator.create_texture_from_surface(&value_surface)?;
+    let value_width = value_surface.width();
+    canvas.copy(
+        &value_texture,
+        None,
+        Some(Rect::new(
+            (SCREEN_WIDTH as i32 - value_width as i32) / 2,
+            260,
+            value_width,
+            32,
+        )),
+    )?;
+
+    let hint_surface = small_font
+        .render("UP/DOWN to adjust  |  ESC to go back")
+        .blended(Color::RGB(200, 200, 200))?;
+    let hint_texture = texture_creator.create_texture_from_surface(&hint_surface)?;
+    let hint_width = hint_surface.width();
+    canvas.copy(
+        &hint_texture,
+        None,
+        Some(Rect::new(
+            (SCREEN_WIDTH as i32 - hint_width as i32) / 2,
+            320,
+            hint_width,
+            32,
+        )),
+    )?;
+
+    Ok(())
+}
+
 // Update the main function to include game over state handling
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -269,9 +830,21 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let username = prompt("Enter Name: ");
     let server_addr = prompt("Enter IP Address (example 127.0.0.1:2025): ");
 
+    // Identity for the `Connect` handshake: the server challenges us with a nonce
+    // once it's provisionally accepted our `JoinGame`, and we prove we own this
+    // key by signing it.
+    let signing_key = SigningKey::generate(&mut OsRng);
+
     let client = NetworkClient::new("0.0.0.0:0", &server_addr)?;
+    // Announce our protocol version before anything else, so an incompatible
+    // server answers with `JoinGameError`. `JoinGame` follows straight away,
+    // without waiting for that answer.
+    client.send(&ClientMessage::Hello {
+        protocol_version: PROTOCOL_VERSION,
+    })?;
     client.send(&ClientMessage::JoinGame {
         username: username.clone(),
+        protocol_version: PROTOCOL_VERSION,
     })?;
 
     let sdl_context = sdl2::init()?;
@@ -288,8 +861,27 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let mut event_pump = sdl_context.event_pump()?;
     let font = ttf_context.load_font("assets/fonts/FiraSans-Bold.ttf", 64)?;
     let small_font = ttf_context.load_font("assets/fonts/FiraSans-Bold.ttf", 32)?;
+    let mut wall_textures = load_wall_textures(&texture_creator);
 
-    let mut maze_map = [[Tile::Wall; MAZE_WIDTH]; MAZE_HEIGHT];
+    let game_controller_subsystem = sdl_context.game_controller()?;
+    let controller = (0..game_controller_subsystem.num_joysticks().unwrap_or(0)).find_map(|id| {
+        game_controller_subsystem
+            .is_game_controller(id)
+            .then(|| game_controller_subsystem.open(id).ok())
+            .flatten()
+    });
+    if let Some(controller) = &controller {
+        println!("🎮 Controller connected: {}", controller.name());
+    }
+
+    // Placeholder until the server's `GameStart` delivers the real level -
+    // solid walls everywhere so there's nothing to walk into before then.
+    let mut maze_map = MazeMap::new(MAZE_WIDTH, MAZE_HEIGHT, 1.0);
+    for y in 0..MAZE_HEIGHT {
+        for x in 0..MAZE_WIDTH {
+            maze_map[y][x] = Tile::Wall(0);
+        }
+    }
     let mut spawns: SpawnPoints = Vec::new();
     let mut maze_level = 1;
 
@@ -300,21 +892,86 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     };
 
     let mut running = true;
-    let mut game_started = false;
-    let mut player_dead = false;
-    let mut game_over = false;
-    let mut winner_name = String::new();
-    let mut spawn_assigned = false;
+    let mut state = GameState::Connecting;
     let mut player_health = 100;
-    let mut other_players: HashMap<String, (Position, Rotation)> = HashMap::new();
+    // Neither reported up front - there's no "starting loadout" message - so
+    // both stay `None` until the first `WeaponSwitch`/`ReloadComplete` naming
+    // our own `my_id` arrives.
+    let mut equipped_index: Option<u8> = None;
+    let mut ammo_count: Option<u32> = None;
+    // The one setting this client actually has - see `render_settings_screen`.
+    let mut turn_sensitivity: f32 = 1.0;
+    let mut my_id: Option<PlayerId> = None;
+    let mut other_players: HashMap<PlayerId, RemotePlayer> = HashMap::new();
     let mut last_frame = Instant::now();
+    let mut last_server_message = Instant::now();
+    // `Move` is only sent when the player actually moves (see below), so an
+    // idle player - sitting in the lobby, or just standing still in-game -
+    // would otherwise never refresh the server's `last_seen` and eventually
+    // get reaped by its heartbeat timeout despite being perfectly connected.
+    let mut last_heartbeat_sent = Instant::now();
     let mut last_sent_position = Position::default();
     let mut last_sent_rotation = Rotation::default();
+    // This client doesn't do prediction/reconciliation, so the sequence number is
+    // purely informational; it just has to keep increasing.
+    let mut input_sequence: u32 = 0;
+
+    // Left-stick axes drive movement, right stick drives turning. Updated
+    // from `ControllerAxisMotion` events (including zero - see the comment
+    // at that match arm) and applied continuously, scaled by frame time,
+    // rather than the one-shot-per-keypress stepping WASD uses.
+    let mut stick_move_x: f32 = 0.0;
+    let mut stick_move_y: f32 = 0.0;
+    let mut stick_turn_x: f32 = 0.0;
 
     while running {
+        let dt = last_frame.elapsed().as_secs_f32();
+
         for event in event_pump.poll_iter() {
             match event {
                 Event::Quit { .. } => running = false,
+                Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } if state == GameState::Playing => {
+                    state = GameState::Paused;
+                }
+                Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } if state == GameState::Paused => {
+                    state = GameState::Playing;
+                }
+                Event::KeyDown {
+                    keycode: Some(Keycode::Q),
+                    ..
+                } if state == GameState::Paused => {
+                    running = false;
+                }
+                Event::KeyDown {
+                    keycode: Some(Keycode::O),
+                    ..
+                } if state == GameState::Paused => {
+                    state = GameState::Settings;
+                }
+                Event::KeyDown {
+                    keycode: Some(Keycode::Escape),
+                    ..
+                } if state == GameState::Settings => {
+                    state = GameState::Paused;
+                }
+                Event::KeyDown {
+                    keycode: Some(Keycode::Up),
+                    ..
+                } if state == GameState::Settings => {
+                    turn_sensitivity = (turn_sensitivity + 0.25).min(3.0);
+                }
+                Event::KeyDown {
+                    keycode: Some(Keycode::Down),
+                    ..
+                } if state == GameState::Settings => {
+                    turn_sensitivity = (turn_sensitivity - 0.25).max(0.25);
+                }
                 Event::KeyDown {
                     keycode: Some(Keycode::Escape),
                     ..
@@ -322,19 +979,25 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 Event::KeyDown {
                     keycode: Some(keycode),
                     ..
-                } if game_over => {
+                } if matches!(state, GameState::GameOver { .. }) => {
                     // Any key press on game over screen exits the game
                     if keycode == Keycode::Return || keycode == Keycode::Space || keycode == Keycode::Escape {
                         running = false;
                     }
                 },
+                Event::KeyDown {
+                    keycode: Some(_),
+                    ..
+                } if matches!(state, GameState::Disconnected { .. }) => {
+                    running = false;
+                }
                 Event::KeyDown {
                     keycode: Some(Keycode::W),
                     ..
-                } if game_started && !player_dead && !game_over => {
+                } if state == GameState::Playing => {
                     let new_x = player.x + player.angle.cos() * 0.1;
                     let new_y = player.y + player.angle.sin() * 0.1;
-                    if maze_map[new_y as usize][new_x as usize] != Tile::Wall {
+                    if !maze_map.is_wall_at(new_x, new_y) {
                         player.x = new_x;
                         player.y = new_y;
                     }
@@ -342,10 +1005,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 Event::KeyDown {
                     keycode: Some(Keycode::S),
                     ..
-                } if game_started && !player_dead && !game_over => {
+                } if state == GameState::Playing => {
                     let new_x = player.x - player.angle.cos() * 0.1;
                     let new_y = player.y - player.angle.sin() * 0.1;
-                    if maze_map[new_y as usize][new_x as usize] != Tile::Wall {
+                    if !maze_map.is_wall_at(new_x, new_y) {
                         player.x = new_x;
                         player.y = new_y;
                     }
@@ -353,50 +1016,153 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 Event::KeyDown {
                     keycode: Some(Keycode::A),
                     ..
-                } if game_started && !player_dead && !game_over => {
-                    player.angle -= 0.1;
+                } if state == GameState::Playing => {
+                    player.angle -= 0.1 * turn_sensitivity;
                 }
                 Event::KeyDown {
                     keycode: Some(Keycode::D),
                     ..
-                } if game_started && !player_dead && !game_over => {
-                    player.angle += 0.1;
+                } if state == GameState::Playing => {
+                    player.angle += 0.1 * turn_sensitivity;
                 }
                 Event::KeyDown {
                     keycode: Some(Keycode::Space),
                     ..
-                } if game_started && !player_dead && !game_over => {
-                    if let Some((target, _)) = find_target_in_crosshair(&player, &other_players) {
-                        client.send(&ClientMessage::ShotPlayer {
-                            player_username: target,
-                        })?;
+                } if state == GameState::Playing => {
+                    // Who (if anyone) this hits is for the server to decide: it
+                    // raycasts `origin`/`direction` against the maze and every
+                    // other player itself.
+                    client.send(&ClientMessage::ShotPlayer {
+                        origin: Position {
+                            x: player.x,
+                            y: player.y,
+                            z: 0.0,
+                        },
+                        direction: Position {
+                            x: player.angle.cos(),
+                            y: player.angle.sin(),
+                            z: 0.0,
+                        },
+                    })?;
+                }
+                Event::KeyDown {
+                    keycode: Some(Keycode::R),
+                    ..
+                } if state == GameState::Playing => {
+                    client.send(&ClientMessage::Reload)?;
+                }
+                Event::KeyDown {
+                    keycode: Some(keycode),
+                    ..
+                } if state == GameState::Playing
+                    && matches!(keycode, Keycode::Num1 | Keycode::Num2 | Keycode::Num3) =>
+                {
+                    let index = match keycode {
+                        Keycode::Num1 => 0,
+                        Keycode::Num2 => 1,
+                        _ => 2,
+                    };
+                    client.send(&ClientMessage::SwitchWeapon { index })?;
+                }
+                // Record every axis reading, including exactly `0` - that's
+                // the event that fires when the stick is released back to
+                // center, and skipping it here would leave the player
+                // drifting in whatever direction it was last pushed.
+                Event::ControllerAxisMotion { axis, value, .. } => {
+                    let normalized = normalize_axis(value);
+                    match axis {
+                        Axis::LeftX => stick_move_x = normalized,
+                        Axis::LeftY => stick_move_y = normalized,
+                        Axis::RightX => stick_turn_x = normalized,
+                        _ => {}
                     }
                 }
+                Event::ControllerButtonDown {
+                    button: Button::A, ..
+                } if state == GameState::Playing => {
+                    client.send(&ClientMessage::ShotPlayer {
+                        origin: Position {
+                            x: player.x,
+                            y: player.y,
+                            z: 0.0,
+                        },
+                        direction: Position {
+                            x: player.angle.cos(),
+                            y: player.angle.sin(),
+                            z: 0.0,
+                        },
+                    })?;
+                }
                 _ => {}
             }
         }
 
+        if state == GameState::Playing {
+            let forward = -apply_dead_zone(stick_move_y);
+            let strafe = apply_dead_zone(stick_move_x);
+            let turn = apply_dead_zone(stick_turn_x);
+
+            if forward != 0.0 || strafe != 0.0 {
+                let move_x = player.angle.cos() * forward - player.angle.sin() * strafe;
+                let move_y = player.angle.sin() * forward + player.angle.cos() * strafe;
+                let new_x = player.x + move_x * CONTROLLER_MOVE_SPEED * dt;
+                let new_y = player.y + move_y * CONTROLLER_MOVE_SPEED * dt;
+                if !maze_map.is_wall_at(new_x, new_y) {
+                    player.x = new_x;
+                    player.y = new_y;
+                }
+            }
+
+            player.angle += turn * CONTROLLER_TURN_SPEED * turn_sensitivity * dt;
+        }
+
+        client.resend_due()?;
+
+        if !matches!(state, GameState::Connecting | GameState::Disconnected { .. })
+            && last_heartbeat_sent.elapsed() > HEARTBEAT_INTERVAL
+        {
+            client.send(&ClientMessage::Heartbeat)?;
+            last_heartbeat_sent = Instant::now();
+        }
+
+        if !matches!(state, GameState::Disconnected { .. })
+            && last_server_message.elapsed() > SERVER_TIMEOUT
+        {
+            state = GameState::Disconnected {
+                message: "Lost connection to the server".to_string(),
+            };
+        }
+
         if let Some(msg) = client.try_receive() {
+            last_server_message = Instant::now();
             match msg {
                 ServerMessage::GameStart { maze_level: level } => {
-                    game_started = true;
                     maze_level = level;
-                    
-                    // Generate the specific level
-                    let level = match maze_level {
-                        1 => level_1(),
-                        2 => level_2(),
-                        3 => level_3(),
-                        _ => level_1(),
-                    };
-                    
+
+                    // Regenerate the exact same procedural maze the server picked,
+                    // from the seed it sent - see `shared::map::generate_procedural_maze`.
+                    let level = generate_procedural_maze(MAZE_WIDTH, MAZE_HEIGHT, maze_level as u64);
+
                     maze_map = level.map;
                     spawns = level.spawns;
-                    
+                    player_health = 100;
+
                     println!("🎮 Game starting with maze level {}", maze_level);
                 },
+                ServerMessage::Meta { player_id, server_name, .. } => {
+                    my_id = Some(player_id);
+                    println!("🔌 Connected to {} as id {}", server_name, player_id);
+                }
+                ServerMessage::Challenge { nonce } => {
+                    let signature = signing_key.sign(&nonce);
+                    client.send(&ClientMessage::Connect {
+                        pubkey: signing_key.verifying_key().to_bytes(),
+                        nonce,
+                        signature: signature.to_bytes(),
+                    })?;
+                }
                 ServerMessage::HealthUpdate { player_id, health } => {
-                    if player_id == username {
+                    if Some(player_id) == my_id {
                         player_health = health;
                     }
                 }
@@ -406,27 +1172,47 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     rotation,
                     ..
                 } => {
-                    if player_id != username {
-                        other_players.insert(player_id, (position, rotation));
+                    if Some(player_id) != my_id {
+                        other_players
+                            .entry(player_id)
+                            .and_modify(|remote| remote.push(position, rotation))
+                            .or_insert_with(|| {
+                                RemotePlayer::new(String::new(), position, rotation)
+                            });
                     }
                 }
                 ServerMessage::PlayerDeath { player_id, .. } => {
-                    if player_id == username {
-                        player_dead = true;
+                    if Some(player_id) == my_id {
+                        state = GameState::Dead;
                         println!("💀 You were killed!");
                     } else {
-                        println!("⚰️ {} was eliminated!", player_id);
+                        println!("⚰️ Player {} was eliminated!", player_id);
                         other_players.remove(&player_id);
                     }
                 }
+                ServerMessage::PlayerDisconnected { player_id } => {
+                    other_players.remove(&player_id);
+                }
+                ServerMessage::WeaponSwitch { player_id, index } => {
+                    if Some(player_id) == my_id {
+                        equipped_index = Some(index);
+                        // A freshly equipped weapon's ammo is unknown until a
+                        // `ReloadComplete` reports it - clear the stale count
+                        // from whatever was equipped before.
+                        ammo_count = None;
+                    }
+                }
+                ServerMessage::ReloadComplete { player_id, ammo_count: refilled } => {
+                    if Some(player_id) == my_id {
+                        ammo_count = Some(refilled);
+                    }
+                }
                 ServerMessage::JoinGameError { message } | ServerMessage::Error { message } => {
-                    println!("❌ Error: {}", message);
-                    running = false;
+                    state = GameState::Disconnected { message };
                 }
                 ServerMessage::GameOver { winner } => {
-                    game_over = true;
-                    winner_name = winner.clone();
                     println!("🏆 Game Over! {} wins!", winner);
+                    state = GameState::GameOver { winner };
                 }
                 ServerMessage::PlayersInLobby {
                     player_count,
@@ -434,14 +1220,23 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 } => {
                     println!("👥 Players in lobby: {}. {:?}", player_count, players);
 
-                    if !spawn_assigned && game_started {
+                    if spawns.is_empty() {
+                        // GameStart hasn't fired yet - just refresh the
+                        // waiting-room roster.
+                        if matches!(state, GameState::Connecting | GameState::Lobby { .. }) {
+                            state = GameState::Lobby { players };
+                        }
+                    } else if matches!(state, GameState::Lobby { .. }) {
+                        // GameStart already fired; this roster is what tells
+                        // us our spawn index, and finding it is the single
+                        // place the Lobby -> Playing transition happens.
                         if let Some(index) = players.iter().position(|p| p == &username) {
                             if index < spawns.len() {
                                 let (x, y) = spawns[index];
                                 player.x = x;
                                 player.y = y;
-                                spawn_assigned = true;
 
+                                input_sequence += 1;
                                 client.send(&ClientMessage::Move {
                                     position: Position {
                                         x: player.x,
@@ -454,7 +1249,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                                         roll: 0.0,
                                     },
                                     yield_control: 0.5,
+                                    input_sequence,
                                 })?;
+
+                                state = GameState::Playing;
                             } else {
                                 println!("⚠️ No spawn available for player index {}", index);
                             }
@@ -476,11 +1274,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             roll: 0.0,
         };
 
-        if !player_dead && !game_over && (position != last_sent_position || rotation != last_sent_rotation) {
+        if state == GameState::Playing && (position != last_sent_position || rotation != last_sent_rotation) {
+            input_sequence += 1;
             client.send(&ClientMessage::Move {
                 position,
                 rotation,
                 yield_control: 0.5,
+                input_sequence,
             })?;
             last_sent_position = position;
             last_sent_rotation = rotation;
@@ -489,62 +1289,93 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         canvas.set_draw_color(Color::RGB(30, 30, 30));
         canvas.clear();
 
-        if game_over {
-            // Render game over screen
-            let game_over_surface = font.render("GAME OVER").blended(Color::RGB(255, 255, 0))?;
-            let game_over_texture = texture_creator.create_texture_from_surface(&game_over_surface)?;
-            let game_over_rect = Rect::new(250, 200, 300, 100);
-            canvas.copy(&game_over_texture, None, Some(game_over_rect))?;
-            
-            // Show winner
-            let winner_text = format!("{} Wins!", winner_name);
-            let winner_surface = small_font.render(&winner_text).blended(Color::RGB(255, 255, 255))?;
-            let winner_texture = texture_creator.create_texture_from_surface(&winner_surface)?;
-            let winner_text_width = winner_surface.width();
-            let winner_rect = Rect::new(
-                (SCREEN_WIDTH - winner_text_width) as i32 / 2, 
-                320, 
-                winner_text_width, 
-                40
-            );
-            canvas.copy(&winner_texture, None, Some(winner_rect))?;
-            
-            // Show instruction to exit
-            let exit_surface = small_font.render("Press ENTER, SPACE or ESC to exit").blended(Color::RGB(200, 200, 200))?;
-            let exit_texture = texture_creator.create_texture_from_surface(&exit_surface)?;
-            let exit_text_width = exit_surface.width();
-            let exit_rect = Rect::new(
-                (SCREEN_WIDTH - exit_text_width) as i32 / 2, 
-                400, 
-                exit_text_width, 
-                40
-            );
-            canvas.copy(&exit_texture, None, Some(exit_rect))?;
-            
-            // If you won, show congratulatory message
-            if winner_name == username {
-                let congrats_surface = small_font.render("Congratulations!").blended(Color::RGB(0, 255, 0))?;
-                let congrats_texture = texture_creator.create_texture_from_surface(&congrats_surface)?;
-                let congrats_text_width = congrats_surface.width();
-                let congrats_rect = Rect::new(
-                    (SCREEN_WIDTH - congrats_text_width) as i32 / 2, 
-                    360, 
-                    congrats_text_width, 
-                    40
-                );
-                canvas.copy(&congrats_texture, None, Some(congrats_rect))?;
+        // Rendered position, not raw received position - see `RemotePlayer`.
+        let rendered_players = interpolated_players(&other_players);
+
+        match &state {
+            GameState::Connecting => {
+                render_lobby_screen(&mut canvas, &texture_creator, &font, &small_font, &[], &username)?;
+            }
+            GameState::Lobby { players } => {
+                render_lobby_screen(&mut canvas, &texture_creator, &font, &small_font, players, &username)?;
             }
-        } else if game_started {
-            render_first_person_view(&mut canvas, &maze_map, &player, &other_players);
-            if !player_dead {
+            GameState::Playing => {
+                render_first_person_view(&mut canvas, &maze_map, &player, &rendered_players, &mut wall_textures);
                 render_health_bar(&mut canvas, player_health);
-                render_minimap_below(&mut canvas, &maze_map, &player, &other_players);
-            } else {
+                render_ammo_hud(&mut canvas, &texture_creator, &small_font, equipped_index, ammo_count)?;
+                render_minimap_below(&mut canvas, &maze_map, &player, &rendered_players);
+                render_radar_hud(&mut canvas, &player, &rendered_players);
+            }
+            GameState::Paused => {
+                render_first_person_view(&mut canvas, &maze_map, &player, &rendered_players, &mut wall_textures);
+                render_health_bar(&mut canvas, player_health);
+                render_pause_overlay(&mut canvas, &texture_creator, &font, &small_font)?;
+            }
+            GameState::Settings => {
+                render_settings_screen(
+                    &mut canvas,
+                    &texture_creator,
+                    &font,
+                    &small_font,
+                    turn_sensitivity,
+                )?;
+            }
+            GameState::Dead => {
+                render_first_person_view(&mut canvas, &maze_map, &player, &rendered_players, &mut wall_textures);
                 let surface = font.render("YOU DIED").blended(Color::RGB(255, 0, 0))?;
                 let texture = texture_creator.create_texture_from_surface(&surface)?;
                 let rect = Rect::new(250, 250, 300, 100);
                 canvas.copy(&texture, None, Some(rect))?;
             }
+            GameState::GameOver { winner } => {
+                // Render game over screen
+                let game_over_surface = font.render("GAME OVER").blended(Color::RGB(255, 255, 0))?;
+                let game_over_texture = texture_creator.create_texture_from_surface(&game_over_surface)?;
+                let game_over_rect = Rect::new(250, 200, 300, 100);
+                canvas.copy(&game_over_texture, None, Some(game_over_rect))?;
+
+                // Show winner
+                let winner_text = format!("{} Wins!", winner);
+                let winner_surface = small_font.render(&winner_text).blended(Color::RGB(255, 255, 255))?;
+                let winner_texture = texture_creator.create_texture_from_surface(&winner_surface)?;
+                let winner_text_width = winner_surface.width();
+                let winner_rect = Rect::new(
+                    (SCREEN_WIDTH - winner_text_width) as i32 / 2,
+                    320,
+                    winner_text_width,
+                    40
+                );
+                canvas.copy(&winner_texture, None, Some(winner_rect))?;
+
+                // Show instruction to exit
+                let exit_surface = small_font.render("Press ENTER, SPACE or ESC to exit").blended(Color::RGB(200, 200, 200))?;
+                let exit_texture = texture_creator.create_texture_from_surface(&exit_surface)?;
+                let exit_text_width = exit_surface.width();
+                let exit_rect = Rect::new(
+                    (SCREEN_WIDTH - exit_text_width) as i32 / 2,
+                    400,
+                    exit_text_width,
+                    40
+                );
+                canvas.copy(&exit_texture, None, Some(exit_rect))?;
+
+                // If you won, show congratulatory message
+                if winner == &username {
+                    let congrats_surface = small_font.render("Congratulations!").blended(Color::RGB(0, 255, 0))?;
+                    let congrats_texture = texture_creator.create_texture_from_surface(&congrats_surface)?;
+                    let congrats_text_width = congrats_surface.width();
+                    let congrats_rect = Rect::new(
+                        (SCREEN_WIDTH - congrats_text_width) as i32 / 2,
+                        360,
+                        congrats_text_width,
+                        40
+                    );
+                    canvas.copy(&congrats_texture, None, Some(congrats_rect))?;
+                }
+            }
+            GameState::Disconnected { message } => {
+                render_disconnect_dialog(&mut canvas, &texture_creator, &font, &small_font, message)?;
+            }
         }
 
         canvas.present();
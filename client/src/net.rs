@@ -1,10 +1,22 @@
 use anyhow::Result;
+use shared::channel::{build_fragments, ChannelHeader, ChannelId, ChannelState, UnackedSend};
 use shared::server::{ClientMessage, ServerMessage};
 use std::net::{ToSocketAddrs, UdpSocket};
+use std::sync::Mutex;
+use std::time::Instant;
+
+fn channel_for(is_reliable: bool) -> ChannelId {
+    if is_reliable {
+        ChannelId::Reliable
+    } else {
+        ChannelId::Unreliable
+    }
+}
 
 pub struct NetworkClient {
     socket: UdpSocket,
     server_addr: String,
+    channel: Mutex<ChannelState>,
 }
 
 impl NetworkClient {
@@ -14,31 +26,81 @@ impl NetworkClient {
         Ok(Self {
             socket,
             server_addr: server_addr.to_string(),
+            channel: Mutex::new(ChannelState::new()),
         })
     }
 
+    /// Resolves the configured server address
+    fn resolve_server_addr(&self) -> Result<std::net::SocketAddr> {
+        self.server_addr
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("Could not resolve server address"))
+    }
+
     pub fn send(&self, msg: &ClientMessage) -> Result<()> {
-        let json = serde_json::to_string(msg)?;
-
-        // Resolve to a valid socket address
-        let mut addrs_iter = self.server_addr.to_socket_addrs()?;
-        if let Some(addr) = addrs_iter.next() {
-            self.socket.send_to(json.as_bytes(), addr)?;
-        } else {
-            return Err(anyhow::anyhow!("Could not resolve server address"));
+        let addr = self.resolve_server_addr()?;
+        let channel = channel_for(msg.is_reliable());
+
+        let mut channel_state = self.channel.lock().unwrap();
+        let seq = channel_state.next_seq(channel);
+        let (ack, ack_bitfield) = channel_state.outgoing_ack();
+
+        let body = bincode::serialize(msg)?;
+        let fragments = build_fragments(channel, seq, ack, ack_bitfield, &body);
+
+        if channel == ChannelId::Reliable {
+            channel_state.unacked.insert(
+                seq,
+                UnackedSend {
+                    fragments: fragments.clone(),
+                    sent_at: Instant::now(),
+                    attempts: 0,
+                },
+            );
         }
+        drop(channel_state);
 
+        for datagram in &fragments {
+            self.socket.send_to(datagram, addr)?;
+        }
+        Ok(())
+    }
+
+    /// Resends any reliable sends the server hasn't acked yet, whose backoff has
+    /// elapsed. Should be called once per frame.
+    pub fn resend_due(&self) -> Result<()> {
+        let addr = self.resolve_server_addr()?;
+        let mut channel_state = self.channel.lock().unwrap();
+        let due = channel_state.due_for_resend();
+        for seq in due {
+            let Some(pending) = channel_state.unacked.get_mut(&seq) else {
+                continue;
+            };
+            pending.attempts += 1;
+            pending.sent_at = Instant::now();
+            for datagram in &pending.fragments {
+                self.socket.send_to(datagram, addr)?;
+            }
+        }
         Ok(())
     }
 
+    /// Reads at most one datagram off the socket this call, feeds it through
+    /// the channel's reassembly/reordering, then returns the next message
+    /// ready for delivery (if any) - which may be one that arrived in an
+    /// earlier call and was buffered behind a gap that just closed.
     pub fn try_receive(&self) -> Option<ServerMessage> {
-        let mut buf = [0u8; 1024];
-        match self.socket.recv_from(&mut buf) {
-            Ok((len, _addr)) => {
-                let msg = String::from_utf8_lossy(&buf[..len]);
-                serde_json::from_str(&msg).ok()
+        let mut buf = [0u8; 4096];
+        let mut channel_state = self.channel.lock().unwrap();
+
+        if let Ok((len, _addr)) = self.socket.recv_from(&mut buf) {
+            if let Some((header, payload)) = ChannelHeader::decode(&buf[..len]) {
+                channel_state.receive(&header, payload);
             }
-            Err(_) => None,
         }
+
+        let bytes = channel_state.pop_ready()?;
+        bincode::deserialize(&bytes).ok()
     }
 }
@@ -1,4 +1,5 @@
 mod server;
+mod transport;
 
 use chrono::Local;
 use colored::*;
@@ -1,30 +1,138 @@
-use shared::Player;
-use std::{collections::HashMap, net::SocketAddr, time::Instant};
 use rand::Rng;
+use serde::{Deserialize, Serialize};
+use shared::{channel::ChannelState, map::MazeMap, server::PlayerId, Player};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::Instant;
 
-#[derive(Debug, PartialEq)]
+use super::handshake::PendingHandshake;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum GameState {
     Waiting,
     InProgress,
     Finished,
 }
 
+#[derive(Debug)]
+/// A connected player together with the address its datagrams arrive from
+pub struct PlayerSlot {
+    pub addr: SocketAddr,
+    pub player: Player,
+    /// Sequencing/ack bookkeeping for this player's reliable and unreliable channels
+    pub channel: ChannelState,
+    /// Whether `handle_move` has applied a `Move` for this player yet. Their
+    /// very first move after joining places them at whatever spawn point the
+    /// client picked, which can be arbitrarily far from the default `(0, 0, 0)`
+    /// `Player::new` starts them at - `handle_move`'s speed clamp only kicks in
+    /// from the second move on, once "distance since last position" actually
+    /// means something.
+    pub has_moved: bool,
+}
+
 #[derive(Debug)]
 pub struct Game {
-    pub players: HashMap<SocketAddr, Player>,
+    /// Slot-allocated players, indexed by their `PlayerId`. A `None` entry is a free
+    /// slot that `alloc_slot` will reuse before growing the vector.
+    pub players: Vec<Option<PlayerSlot>>,
     pub state: GameState,
     pub game_start_time: Option<Instant>,
+    /// Seed for the procedurally generated maze (`shared::map::generate_procedural_maze`),
+    /// sent to every client in `GameStart` so each one regenerates the exact
+    /// same layout locally instead of the server shipping the whole grid.
     pub maze_level: u8,
+    /// Wall layout generated from `maze_level`, built once at construction so
+    /// `handle_shoot` can raycast against the same grid every client renders
+    pub maze: MazeMap,
+    /// Monotonically increasing counter for the fixed-rate world tick loop, stamped
+    /// on every `ServerMessage::WorldFrame`
+    pub tick: u32,
+    /// Highest `Move` input sequence applied per player, echoed back in
+    /// `WorldFrame` so each client knows which of its predicted inputs to discard
+    pub last_processed_input: HashMap<PlayerId, u32>,
+    /// `JoinGame`s awaiting their matching `Connect`, keyed by the address the
+    /// `JoinGame` arrived from
+    pub pending_handshakes: HashMap<SocketAddr, PendingHandshake>,
+    /// Last known state of players from a restored `GameSnapshot`, keyed by
+    /// username. `handle_connect` consumes an entry for a reconnecting player
+    /// instead of spawning them fresh, so a resumed match picks up where they
+    /// left off rather than respawning everyone at default stats.
+    pub restorable_players: HashMap<String, Player>,
 }
 
 impl Game {
     pub fn new() -> Self {
-        let maze_level = (rand::thread_rng().gen_range(0..3) + 1) as u8;
+        let maze_level: u8 = rand::thread_rng().gen();
+        let maze = shared::map::generate_procedural_maze(
+            shared::map::MAZE_WIDTH,
+            shared::map::MAZE_HEIGHT,
+            maze_level as u64,
+        )
+        .map;
         Game {
-            players: HashMap::new(),
+            players: Vec::new(),
             state: GameState::Waiting,
             game_start_time: None,
             maze_level,
+            maze,
+            tick: 0,
+            last_processed_input: HashMap::new(),
+            pending_handshakes: HashMap::new(),
+            restorable_players: HashMap::new(),
+        }
+    }
+
+    /// Number of currently connected players
+    pub fn player_count(&self) -> usize {
+        self.players.iter().filter(|s| s.is_some()).count()
+    }
+
+    /// Allocates the lowest free slot for a newly joined player, reusing one freed by
+    /// a disconnect before growing the vector
+    pub fn alloc_slot(&mut self, addr: SocketAddr, player: Player) -> PlayerId {
+        let slot = PlayerSlot {
+            addr,
+            player,
+            channel: ChannelState::new(),
+            has_moved: false,
+        };
+        if let Some(id) = self.players.iter().position(|s| s.is_none()) {
+            self.players[id] = Some(slot);
+            id as PlayerId
+        } else {
+            self.players.push(Some(slot));
+            (self.players.len() - 1) as PlayerId
         }
     }
+
+    /// Finds the slot id belonging to a given socket address
+    pub fn id_for_addr(&self, addr: &SocketAddr) -> Option<PlayerId> {
+        self.players.iter().enumerate().find_map(|(id, slot)| {
+            slot.as_ref()
+                .filter(|s| &s.addr == addr)
+                .map(|_| id as PlayerId)
+        })
+    }
+
+    pub fn get(&self, id: PlayerId) -> Option<&PlayerSlot> {
+        self.players.get(id as usize).and_then(|s| s.as_ref())
+    }
+
+    pub fn get_mut(&mut self, id: PlayerId) -> Option<&mut PlayerSlot> {
+        self.players.get_mut(id as usize).and_then(|s| s.as_mut())
+    }
+
+    /// Frees a slot, making it available for reuse by the next `alloc_slot`
+    pub fn remove(&mut self, id: PlayerId) -> Option<PlayerSlot> {
+        self.last_processed_input.remove(&id);
+        self.players.get_mut(id as usize).and_then(|s| s.take())
+    }
+
+    /// Iterates over currently occupied slots as `(id, &PlayerSlot)` pairs
+    pub fn iter(&self) -> impl Iterator<Item = (PlayerId, &PlayerSlot)> {
+        self.players
+            .iter()
+            .enumerate()
+            .filter_map(|(id, s)| s.as_ref().map(|slot| (id as PlayerId, slot)))
+    }
 }
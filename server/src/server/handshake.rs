@@ -0,0 +1,35 @@
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use rand::RngCore;
+use std::time::Instant;
+
+/// A `JoinGame` that's been provisionally accepted but hasn't yet proven it owns
+/// the pubkey it's about to claim. Held until the matching `ClientMessage::Connect`
+/// arrives (or the handshake times out and the entry is abandoned).
+#[derive(Debug)]
+pub struct PendingHandshake {
+    pub username: String,
+    pub nonce: [u8; 32],
+    pub issued_at: Instant,
+}
+
+/// How long a client has to answer a `Challenge` before its slot is given up.
+pub const HANDSHAKE_TIMEOUT_SECS: u64 = 10;
+
+/// Generates a fresh, unpredictable nonce for a new `Challenge`.
+pub fn generate_nonce() -> [u8; 32] {
+    let mut nonce = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    nonce
+}
+
+/// Verifies that `signature` is a valid ed25519 signature over `nonce` under `pubkey`,
+/// i.e. that whoever sent `Connect` actually holds the private key for `pubkey`.
+/// Whether `nonce` is the one this server issued is not checked here;
+/// `handle_connect` compares it against the pending handshake.
+pub fn verify_connect(pubkey: &[u8; 32], nonce: &[u8; 32], signature: &[u8; 64]) -> bool {
+    let Ok(verifying_key) = VerifyingKey::from_bytes(pubkey) else {
+        return false;
+    };
+    let signature = Signature::from_bytes(signature);
+    verifying_key.verify(nonce, &signature).is_ok()
+}
@@ -1,19 +1,77 @@
-use shared::{server::ServerMessage, Player, Position, Rotation, Weapon};
-use std::{net::SocketAddr, sync::Arc, time::Instant};
-use tokio::net::UdpSocket;
+use shared::{
+    server::{ServerMessage, PROTOCOL_VERSION},
+    transport::{PeerId, Transport},
+    Player, Position, Rotation, Weapon,
+};
+use std::{
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio::sync::Mutex;
 
 use super::{
     game::{Game, GameState},
-    Server,
+    handshake, Server,
 };
 
+/// World units per second an unencumbered player can cover, before their
+/// equipped weapon's `speed_multiplier` scales it down - used by `handle_move`
+/// to bound how far a claimed position can have moved since the last one.
+const BASE_MOVE_SPEED: f32 = 6.0;
+/// Slack added on top of `BASE_MOVE_SPEED * dt` in `handle_move`'s travel-
+/// distance check, to absorb network jitter and the gap between a `Move`'s
+/// `dt` and the real wall-clock time the client simulated - not a distance
+/// any legitimate single move should need beyond that budget.
+const MOVE_TOLERANCE: f32 = 1.0;
+/// Max world-unit drift allowed, one unit out along each ray, between a
+/// shot's claimed `direction` and the shooter's own last known facing
+/// (`Rotation::forward_vector`, refreshed by every `Move`) in `handle_shoot`.
+/// Generous enough to absorb a `ShotPlayer` arriving just before the `Move`
+/// that would have updated `rotation` to match it, but tight enough that a
+/// direction unrelated to where the player was actually looking gets rejected.
+const MAX_AIM_DRIFT: f32 = 1.0;
+
 impl Server {
-    /// Handles new player joining the game
+    /// Handles the very first message a client sends, before it has a username or
+    /// a pending handshake. Only checks the protocol version so an incompatible
+    /// client is turned away with `JoinGameError` before `handle_join_game` would
+    /// otherwise bother issuing it a challenge nonce.
+    ///
+    /// # Arguments
+    /// * `transport` - Transport to send a reply over
+    /// * `addr` - Client's network address
+    /// * `protocol_version` - Protocol version the client claims to speak
+    ///
+    /// # Returns
+    /// Result indicating success or failure
+    pub async fn handle_hello(
+        &self,
+        transport: Arc<dyn Transport>,
+        addr: SocketAddr,
+        protocol_version: u32,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if protocol_version != PROTOCOL_VERSION {
+            let peer = PeerId(addr.to_string());
+            let error_message = ServerMessage::JoinGameError {
+                message: format!(
+                    "Protocol version mismatch: server is {}, client is {}",
+                    PROTOCOL_VERSION, protocol_version
+                ),
+            };
+            self.send_message(&transport, error_message, &peer).await?;
+        }
+        Ok(())
+    }
+
+    /// Handles a player's initial join request by provisionally accepting it and
+    /// challenging it to prove ownership of the pubkey it'll claim in the
+    /// follow-up `ClientMessage::Connect`. No `Player` is created yet - that
+    /// happens in `handle_connect` once the signature checks out.
     ///
     /// # Arguments
     /// * `game_state` - Shared game state
-    /// * `socket` - UDP socket reference
+    /// * `transport` - Transport to send replies over
     /// * `addr` - Client's network address
     /// * `username` - Player's chosen name
     ///
@@ -22,43 +80,152 @@ impl Server {
     pub async fn handle_join_game(
         &self,
         game_state: Arc<Mutex<Game>>,
-        socket: Arc<UdpSocket>,
+        transport: Arc<dyn Transport>,
         addr: SocketAddr,
         username: String,
+        protocol_version: u32,
     ) -> Result<(), Box<dyn std::error::Error>> {
+        let peer = PeerId(addr.to_string());
+
+        // Refuse the join outright on a protocol mismatch instead of registering a
+        // player the rest of the server can't reliably talk to
+        if protocol_version != PROTOCOL_VERSION {
+            let error_message = ServerMessage::Error {
+                message: format!(
+                    "Protocol version mismatch: server is {}, client is {}",
+                    PROTOCOL_VERSION, protocol_version
+                ),
+            };
+            self.send_message(&transport, error_message, &peer).await?;
+            return Ok(());
+        }
+
         let mut state = game_state.lock().await;
 
         // Check if the player with the same name already exists
-        if state.players.values().any(|p| p.username == username) {
+        if state.iter().any(|(_, slot)| slot.player.username == username) {
             let error_message = ServerMessage::JoinGameError {
                 message: "Username already taken".to_string(),
             };
-            self.send_message(&socket, error_message, &addr).await?;
+            self.send_message(&transport, error_message, &peer).await?;
             return Ok(());
         }
 
         // Check if the player limit is reached
-        if state.players.len() >= self.max_players as usize {
+        if state.player_count() + state.pending_handshakes.len() >= self.max_players as usize {
             let error_message = ServerMessage::JoinGameError {
                 message: "Server is full".to_string(),
             };
-            self.send_message(&socket, error_message, &addr).await?;
+            self.send_message(&transport, error_message, &peer).await?;
+            return Ok(());
+        }
+
+        let nonce = handshake::generate_nonce();
+        state.pending_handshakes.insert(
+            addr,
+            handshake::PendingHandshake {
+                username,
+                nonce,
+                issued_at: Instant::now(),
+            },
+        );
+
+        self.send_message(&transport, ServerMessage::Challenge { nonce }, &peer)
+            .await?;
+        Ok(())
+    }
+
+    /// Completes a handshake: verifies the signature over the challenge nonce
+    /// this server issued in `handle_join_game`, and only then creates the
+    /// `Player` and admits it to the lobby.
+    ///
+    /// # Arguments
+    /// * `game_state` - Shared game state
+    /// * `transport` - Transport to send replies over
+    /// * `addr` - Client's network address
+    /// * `pubkey` - Claimed ed25519 public key, stored as the player's durable identity
+    /// * `nonce` - Nonce being echoed back, expected to match the one this server issued
+    /// * `signature` - Signature over `nonce` under `pubkey`
+    ///
+    /// # Returns
+    /// Result indicating success or failure
+    pub async fn handle_connect(
+        &self,
+        game_state: Arc<Mutex<Game>>,
+        transport: Arc<dyn Transport>,
+        addr: SocketAddr,
+        pubkey: [u8; 32],
+        nonce: [u8; 32],
+        signature: [u8; 64],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let peer = PeerId(addr.to_string());
+        let mut state = game_state.lock().await;
+
+        let Some(pending) = state.pending_handshakes.remove(&addr) else {
+            log::warn!("Connect from {} with no pending handshake", addr);
+            return Ok(());
+        };
+
+        if pending.nonce != nonce || !handshake::verify_connect(&pubkey, &nonce, &signature) {
+            log::warn!("Connect from {} failed signature verification", addr);
+            let error_message = ServerMessage::JoinGameError {
+                message: "Handshake verification failed".to_string(),
+            };
+            self.send_message(&transport, error_message, &peer).await?;
             return Ok(());
         }
 
-        let player = Player::new(
-            username.clone(),
-            Default::default(),
-            Player::DEFAULT_HEIGHT,
-            Default::default(),
-            100,
-            Weapon::pistol(),
+        let username = pending.username;
+        // A username with a restored entry is picking a resumed match back up -
+        // seed it from its last known state instead of spawning it fresh.
+        let player = match state.restorable_players.remove(&username) {
+            Some(mut restored) => {
+                restored.pubkey = pubkey;
+                restored
+            }
+            None => Player::new(
+                username.clone(),
+                Default::default(),
+                Player::DEFAULT_HEIGHT,
+                Default::default(),
+                100,
+                vec![Weapon::pistol(), Weapon::rifle(), Weapon::sniper()],
+                pubkey,
+            ),
+        };
+        let rejoining_mid_game = state.state == GameState::InProgress;
+        let player_id = state.alloc_slot(addr, player);
+        log::info!(
+            "New player connection: {} from {} (id {})",
+            username,
+            addr,
+            player_id
         );
-        state.players.insert(addr, player);
-        log::info!("New player connection: {} from {}", username, addr);
+
+        let greeting = ServerMessage::Meta {
+            protocol_version: PROTOCOL_VERSION,
+            server_name: "Maze Wars".to_string(),
+            player_id,
+        };
+        Self::send_to_slot(&transport, greeting, state.get_mut(player_id).unwrap()).await?;
+
+        // Catch a mid-match joiner up on everyone else's state immediately,
+        // instead of leaving them to learn it incrementally from the next
+        // `PlayerMove` each other player happens to send.
+        if rejoining_mid_game {
+            let snapshot = ServerMessage::GameSnapshot {
+                players: state
+                    .iter()
+                    .filter(|(id, _)| *id != player_id)
+                    .map(|(_, slot)| slot.player.clone())
+                    .collect(),
+                tick: state.tick as u64,
+            };
+            Self::send_to_slot(&transport, snapshot, state.get_mut(player_id).unwrap()).await?;
+        }
 
         // Start timer if we have enough players but game hasn't started yet
-        let player_count = state.players.len();
+        let player_count = state.player_count();
         if player_count >= self.min_players as usize
             && player_count <= self.max_players as usize
             && state.state == GameState::Waiting
@@ -68,17 +235,22 @@ impl Server {
             state.game_start_time = Some(Instant::now());
 
             // Inform players about the timer
-            let info_message = ServerMessage::GameStart;
-            self.broadcast_message(&socket, info_message, &state.players)
+            let info_message = ServerMessage::GameStart {
+                maze_level: state.maze_level,
+            };
+            self.broadcast_message(&transport, info_message, &mut state.players)
                 .await?;
         }
 
         let response = ServerMessage::PlayersInLobby {
-            player_count: state.players.len() as u32,
-            players: state.players.values().map(|p| p.username.clone()).collect(),
+            player_count: state.player_count() as u32,
+            players: state
+                .iter()
+                .map(|(_, slot)| slot.player.username.clone())
+                .collect(),
         };
 
-        self.broadcast_message(&socket, response, &state.players)
+        self.broadcast_message(&transport, response, &mut state.players)
             .await?;
         Ok(())
     }
@@ -87,41 +259,75 @@ impl Server {
     ///
     /// # Arguments
     /// * `game_state` - Shared game state
-    /// * `socket` - UDP socket reference
+    /// * `transport` - Transport to broadcast the update over
     /// * `addr` - Client's network address
     /// * `position` - New 3D position
     /// * `rotation` - New orientation
     /// * `yield_control` - Movement control value
+    /// * `input_sequence` - Sequence number of the input that produced this move
     ///
     /// # Returns
     /// Result indicating success or failure
     pub async fn handle_move(
         &self,
         game_state: Arc<Mutex<Game>>,
-        socket: Arc<UdpSocket>,
+        transport: Arc<dyn Transport>,
         addr: SocketAddr,
         position: Position,
         rotation: Rotation,
         yield_control: f32,
+        input_sequence: u32,
     ) -> Result<(), Box<dyn std::error::Error>> {
         let mut state = game_state.lock().await;
-        if let Some(player) = state.players.get_mut(&addr) {
-            player.position = position;
-            player.rotation = rotation;
+        let Some(player_id) = state.id_for_addr(&addr) else {
+            return Ok(());
+        };
+        state.last_processed_input.insert(player_id, input_sequence);
+        if let Some(slot) = state.get_mut(player_id) {
+            let dt = slot.player.last_seen.elapsed().as_secs_f32();
+            slot.player.touch();
+
+            // Heavier weapons really do slow a player down: past their first
+            // move (see `PlayerSlot::has_moved`), clamp how far this move can
+            // have traveled since their last one by their equipped weapon's
+            // `speed_multiplier`, instead of trusting the claimed position
+            // outright.
+            let position = if slot.has_moved {
+                let max_distance =
+                    BASE_MOVE_SPEED * slot.player.weapon().speed_multiplier() * dt + MOVE_TOLERANCE;
+                let traveled = slot.player.position.distance(&position);
+                if traveled > max_distance {
+                    let direction = Position::new(
+                        (position.x - slot.player.position.x) / traveled,
+                        (position.y - slot.player.position.y) / traveled,
+                        (position.z - slot.player.position.z) / traveled,
+                    );
+                    slot.player.position.offset(direction, max_distance)
+                } else {
+                    position
+                }
+            } else {
+                slot.has_moved = true;
+                position
+            };
+
+            slot.player.position = position;
+            slot.player.rotation = rotation;
             log::debug!(
                 "Player {} moved to {:?} facing {:?}",
-                player.username,
+                slot.player.username,
                 position,
                 rotation
             );
 
             let response = ServerMessage::PlayerMove {
-                player_id: player.username.clone(),
-                position: player.position,
-                rotation: player.rotation,
+                player_id,
+                position: slot.player.position,
+                rotation: slot.player.rotation,
                 yield_control,
             };
-            self.broadcast_message(&socket, response, &state.players)
+            // The mover already knows its own new position locally; no need to echo it back
+            self.broadcast_message_except(&transport, response, &mut state.players, Some(addr))
                 .await?;
         }
         Ok(())
@@ -129,89 +335,264 @@ impl Server {
 
     /// Processes player shooting actions
     ///
+    /// Trusts nothing about who was hit: the client only reports where the shot
+    /// came from and where it's aimed, and the server raycasts against the maze
+    /// and every other player itself (see `super::raycast`) to find the closest
+    /// one actually in the line of fire. It doesn't fully trust the claimed aim
+    /// either - `direction` is rejected if it diverges too far from the
+    /// shooter's own last known facing (`Rotation::forward_vector`, kept in
+    /// sync by `handle_move`), so a forged direction unrelated to where the
+    /// player was actually looking can't be used to snipe around a wall.
+    ///
     /// # Arguments
     /// * `game_state` - Shared game state
-    /// * `socket` - UDP socket reference
+    /// * `transport` - Transport to broadcast the resulting events over
     /// * `addr` - Client's network address
-    /// * `position` - Shot origin position
-    /// * `direction` - Shooting direction
-    /// * `weapon_type` - Weapon identifier string
+    /// * `origin` - World-space position the shot was fired from
+    /// * `direction` - Normalized aim direction
     ///
     /// # Returns
     /// Result indicating success or failure
     pub async fn handle_shoot(
         &self,
         game_state: Arc<Mutex<Game>>,
-        socket: Arc<UdpSocket>,
+        transport: Arc<dyn Transport>,
         addr: SocketAddr,
-        player_to_shoot: String,
+        origin: Position,
+        direction: Position,
     ) -> Result<(), Box<dyn std::error::Error>> {
         let mut state = game_state.lock().await;
-        let shooter_username = match state.players.get(&addr) {
-            Some(p) => p.username.clone(),
-            None => {
-                log::warn!("Shooter not found for address {}", addr);
-                return Ok(());
-            }
+        let Some(shooter_id) = state.id_for_addr(&addr) else {
+            log::warn!("Shooter not found for address {}", addr);
+            return Ok(());
         };
+        let shooter_slot = state.get_mut(shooter_id).unwrap();
+        shooter_slot.player.touch();
 
-        // Find the address of the player to shoot by username
-        let target_addr = match state
-            .players
-            .iter()
-            .find(|(_, p)| p.username == player_to_shoot)
-        {
-            Some((addr, _)) => *addr,
-            None => {
-                log::warn!("Player to shoot not found: {}", player_to_shoot);
-                return Ok(());
-            }
+        // `wall_distance`'s DDA step and `sphere_hit_distance`'s quadratic both
+        // assume `direction` is already a unit vector, and so does the
+        // aim-drift check just below - normalize it here, once, rather than
+        // trusting the client's claim everywhere downstream.
+        let Some(direction) = direction.normalized() else {
+            log::debug!("Player {} fired with a zero-length direction", shooter_id);
+            return Ok(());
+        };
+
+        if shooter_slot.player.weapon().ammo_count == 0 {
+            log::debug!("Player {} fired with an empty weapon", shooter_id);
+            return Ok(());
+        }
+
+        // Ray-march a unit step along the claimed `direction` and along the
+        // shooter's own last known facing; if the two land far apart, `direction`
+        // doesn't reflect where this player was actually looking.
+        let known_facing = shooter_slot.player.rotation.forward_vector();
+        let claimed_aim_point = origin.offset(direction, 1.0);
+        let known_aim_point = origin.offset(known_facing, 1.0);
+        if claimed_aim_point.distance(&known_aim_point) > MAX_AIM_DRIFT {
+            log::debug!(
+                "Player {} fired in a direction that doesn't match their last known facing",
+                shooter_id
+            );
+            return Ok(());
+        }
+
+        shooter_slot.player.weapon_mut().ammo_count -= 1;
+        let weapon_range = shooter_slot.player.weapon().range;
+        let weapon_damage = shooter_slot.player.weapon().damage;
+
+        let wall_distance = super::raycast::wall_distance(&state.maze, origin, direction);
+        let Some(target_id) = super::raycast::closest_player_hit(
+            shooter_id,
+            origin,
+            direction,
+            wall_distance,
+            state.iter(),
+        ) else {
+            log::debug!("Player {} fired and hit nothing", shooter_id);
+            return Ok(());
         };
 
-        // Extract the necessary information and update the player in a separate scope
-        let (target_username, target_health) = {
-            let target_player = state.players.get_mut(&target_addr).unwrap();
-            // Reduce health by 10, saturating at 0
-            target_player.health = target_player.health.saturating_sub(10);
-            (target_player.username.clone(), target_player.health)
+        // Even a target in the line of fire is a miss if it's further away
+        // than the shooter's equipped weapon can reach.
+        if origin.distance(&state.get(target_id).unwrap().player.position) > weapon_range {
+            log::debug!(
+                "Player {} hit {} out of their weapon's range",
+                shooter_id,
+                target_id
+            );
+            return Ok(());
+        }
+
+        // Reduce health by the shooter's equipped weapon's damage, saturating at 0
+        let target_health = {
+            let target_slot = state.get_mut(target_id).unwrap();
+            target_slot.player.health = target_slot.player.health.saturating_sub(weapon_damage);
+            target_slot.player.health
         };
 
         log::debug!(
             "Player {} fired at {} (new health: {})",
-            shooter_username,
-            target_username,
+            shooter_id,
+            target_id,
             target_health
         );
 
         // Emit HealthUpdate to all players
         let health_update = ServerMessage::HealthUpdate {
-            player_id: target_username.clone(),
+            player_id: target_id,
             health: target_health,
         };
-        self.broadcast_message(&socket, health_update, &state.players)
+        self.broadcast_message(&transport, health_update, &mut state.players)
             .await?;
 
         // If health reaches 0, emit PlayerDeath to all players
         if target_health == 0 {
             let death_message = ServerMessage::PlayerDeath {
-                player_id: target_username,
-                killer_id: Some(shooter_username),
+                player_id: target_id,
+                killer_id: Some(shooter_id),
             };
-            self.broadcast_message(&socket, death_message, &state.players)
+            self.broadcast_message(&transport, death_message, &mut state.players)
                 .await?;
         }
-        // If one player is left alive, emit GameOver
-        // Count alive players (health > 0)
-        let alive_players: Vec<_> = state.players.values().filter(|p| p.health > 0).collect();
 
         // If only one player is alive, emit GameOver
+        let alive_players: Vec<_> = state
+            .iter()
+            .filter(|(_, slot)| slot.player.health > 0)
+            .collect();
         if alive_players.len() == 1 {
-            let winner = alive_players[0].username.clone();
+            let winner = alive_players[0].1.player.username.clone();
             let game_over_message = ServerMessage::GameOver { winner };
-            self.broadcast_message(&socket, game_over_message, &state.players)
+            self.broadcast_message(&transport, game_over_message, &mut state.players)
                 .await?;
         }
 
         Ok(())
     }
+
+    /// Replies to a latency probe and refreshes the sender's liveness timestamp
+    ///
+    /// # Arguments
+    /// * `game_state` - Shared game state
+    /// * `transport` - Transport to send the reply over
+    /// * `addr` - Client's network address
+    /// * `client_time` - Opaque timestamp to echo back unmodified
+    ///
+    /// # Returns
+    /// Result indicating success or failure
+    pub async fn handle_ping(
+        &self,
+        game_state: Arc<Mutex<Game>>,
+        transport: Arc<dyn Transport>,
+        addr: SocketAddr,
+        client_time: u64,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut state = game_state.lock().await;
+        let Some(player_id) = state.id_for_addr(&addr) else {
+            return Ok(());
+        };
+        let slot = state.get_mut(player_id).unwrap();
+        slot.player.touch();
+        Self::send_to_slot(&transport, ServerMessage::Pong { client_time }, slot).await?;
+        Ok(())
+    }
+
+    /// Equips the weapon at `index` in the sender's inventory and tells every
+    /// client, the sender included, so each view of that player's held weapon
+    /// stays in sync.
+    ///
+    /// # Arguments
+    /// * `game_state` - Shared game state
+    /// * `transport` - Transport to broadcast the update over
+    /// * `addr` - Client's network address
+    /// * `index` - Inventory slot to equip
+    ///
+    /// # Returns
+    /// Result indicating success or failure
+    pub async fn handle_switch_weapon(
+        &self,
+        game_state: Arc<Mutex<Game>>,
+        transport: Arc<dyn Transport>,
+        addr: SocketAddr,
+        index: u8,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut state = game_state.lock().await;
+        let Some(player_id) = state.id_for_addr(&addr) else {
+            return Ok(());
+        };
+        let slot = state.get_mut(player_id).unwrap();
+        slot.player.touch();
+        if (index as usize) >= slot.player.inventory.len() {
+            log::warn!(
+                "Player {} tried to switch to out-of-range weapon index {}",
+                player_id,
+                index
+            );
+            return Ok(());
+        }
+        slot.player.equipped = index as usize;
+
+        let response = ServerMessage::WeaponSwitch { player_id, index };
+        self.broadcast_message(&transport, response, &mut state.players)
+            .await?;
+        Ok(())
+    }
+
+    /// Begins reloading the sender's equipped weapon: refills its `ammo_count`
+    /// back to `magazine_size` once that weapon's `reload_time` has elapsed,
+    /// then broadcasts `ServerMessage::ReloadComplete`. The wait happens in a
+    /// detached task so this handler (and the message loop behind it) isn't
+    /// blocked for the duration of the reload.
+    ///
+    /// # Arguments
+    /// * `game_state` - Shared game state
+    /// * `transport` - Transport to broadcast the completion over
+    /// * `addr` - Client's network address
+    ///
+    /// # Returns
+    /// Result indicating success or failure
+    pub async fn handle_reload(
+        &self,
+        game_state: Arc<Mutex<Game>>,
+        transport: Arc<dyn Transport>,
+        addr: SocketAddr,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let (player_id, reload_time) = {
+            let mut state = game_state.lock().await;
+            let Some(player_id) = state.id_for_addr(&addr) else {
+                return Ok(());
+            };
+            let slot = state.get_mut(player_id).unwrap();
+            slot.player.touch();
+            (player_id, slot.player.weapon().reload_time)
+        };
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs_f32(reload_time)).await;
+            let mut state = game_state.lock().await;
+            let Some(slot) = state.get_mut(player_id) else {
+                // Player disconnected mid-reload; nothing left to refill or tell.
+                return;
+            };
+            let ammo_count = slot.player.weapon().magazine_size;
+            slot.player.weapon_mut().ammo_count = ammo_count;
+
+            let message = ServerMessage::ReloadComplete {
+                player_id,
+                ammo_count,
+            };
+            if let Err(e) =
+                Self::broadcast_message_static(&transport, message, &mut state.players).await
+            {
+                log::warn!(
+                    "Failed to broadcast reload completion for {}: {}",
+                    player_id,
+                    e
+                );
+            }
+        });
+
+        Ok(())
+    }
 }
@@ -1,67 +1,147 @@
+use shared::channel::{build_fragments, ChannelHeader, ChannelId, UnackedSend};
 use shared::server::ServerMessage;
-use shared::Player;
-use std::collections::HashMap;
+use shared::transport::{DeliveryGuarantee, PeerId, Transport};
 use std::net::SocketAddr;
 use std::sync::Arc;
-use tokio::net::UdpSocket;
+use std::time::Instant;
 
+use super::game::PlayerSlot;
 use super::Server;
 
+fn channel_for(message: &ServerMessage) -> ChannelId {
+    if message.is_reliable() {
+        ChannelId::Reliable
+    } else {
+        ChannelId::Unreliable
+    }
+}
+
+fn guarantee_for(channel: ChannelId) -> DeliveryGuarantee {
+    match channel {
+        ChannelId::Reliable => DeliveryGuarantee::Reliable,
+        ChannelId::Unreliable => DeliveryGuarantee::Unreliable,
+    }
+}
+
 impl Server {
-    /// Sends a message to a specific player
+    /// Sends a message to a peer that doesn't have a `PlayerSlot` yet, e.g. a join
+    /// rejected before the player is registered. Always best-effort with a zeroed
+    /// ack, since there's no `ChannelState` to track a reliable send against.
     ///
     /// # Arguments
-    /// * `socket` - Reference to UDP socket
-    /// * `message` - ServerMessage to be serialized and sent
-    /// * `addr` - Player's network address
+    /// * `transport` - Transport to send the message over
+    /// * `message` - ServerMessage to be encoded and sent
+    /// * `peer` - Recipient peer id
     ///
     /// # Returns
     /// Result indicating success or failure
     pub async fn send_message(
         &self,
-        socket: &Arc<UdpSocket>,
+        transport: &Arc<dyn Transport>,
         message: ServerMessage,
-        addr: &SocketAddr,
+        peer: &PeerId,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        let json = serde_json::to_string(&message)?;
-        log::trace!("Sending message to {}", addr);
-        socket.send_to(json.as_bytes(), addr).await?;
+        // Stamped unreliable whatever the variant: a reliable seq 0 here would
+        // be taken by the receiver as the first message of the slot's reliable
+        // sequence, and the slot's real seq 0 would then be dropped as a duplicate.
+        let header = ChannelHeader {
+            channel: ChannelId::Unreliable,
+            seq: 0,
+            ack: 0,
+            ack_bitfield: 0,
+            frag_index: 0,
+            frag_count: 1,
+        };
+        let mut datagram = header.encode().to_vec();
+        datagram.extend(bincode::serialize(&message)?);
+
+        log::trace!("Sending message to {}", peer);
+        transport
+            .send(peer, &datagram, DeliveryGuarantee::Unreliable)
+            .await?;
+        Ok(())
+    }
+
+    /// Sends `message` to `slot` on the channel its variant belongs to (see
+    /// `ServerMessage::is_reliable`), stamping the header with `slot`'s next seq
+    /// and its current ack of the peer's reliable traffic. Reliable sends are
+    /// buffered in `slot.channel.unacked` for the resend task to retry.
+    pub(crate) async fn send_to_slot(
+        transport: &Arc<dyn Transport>,
+        message: ServerMessage,
+        slot: &mut PlayerSlot,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let peer = PeerId(slot.addr.to_string());
+        let channel = channel_for(&message);
+        let seq = slot.channel.next_seq(channel);
+        let (ack, ack_bitfield) = slot.channel.outgoing_ack();
+
+        let body = bincode::serialize(&message)?;
+        let fragments = build_fragments(channel, seq, ack, ack_bitfield, &body);
+
+        if channel == ChannelId::Reliable {
+            slot.channel.unacked.insert(
+                seq,
+                UnackedSend {
+                    fragments: fragments.clone(),
+                    sent_at: Instant::now(),
+                    attempts: 0,
+                },
+            );
+        }
+
+        for datagram in &fragments {
+            transport.send(&peer, datagram, guarantee_for(channel)).await?;
+        }
         Ok(())
     }
 
     /// Broadcasts a message to all connected players
     ///
     /// # Arguments
-    /// * `socket` - Reference to UDP socket
-    /// * `message` - ServerMessage to be serialized and broadcasted
-    /// * `players` - Map of connected players
+    /// * `transport` - Transport to send the message over
+    /// * `message` - ServerMessage to be encoded and broadcasted
+    /// * `players` - Slot-allocated table of connected players
     ///
     /// # Returns
     /// Result indicating success or failure
     pub async fn broadcast_message(
         &self,
-        socket: &Arc<UdpSocket>,
+        transport: &Arc<dyn Transport>,
         message: ServerMessage,
-        players: &HashMap<SocketAddr, Player>,
+        players: &mut [Option<PlayerSlot>],
     ) -> Result<(), Box<dyn std::error::Error>> {
-        log::trace!("Broadcasting message to {} players", players.len());
-        for client_addr in players.keys() {
-            self.send_message(socket, message.clone(), client_addr)
-                .await?;
+        self.broadcast_message_except(transport, message, players, None)
+            .await
+    }
+
+    /// Like `broadcast_message`, but skips `skip_addr` (e.g. a player's own echoed
+    /// movement update, which it already knows about locally)
+    pub async fn broadcast_message_except(
+        &self,
+        transport: &Arc<dyn Transport>,
+        message: ServerMessage,
+        players: &mut [Option<PlayerSlot>],
+        skip_addr: Option<SocketAddr>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        for slot in players.iter_mut().flatten() {
+            if Some(slot.addr) == skip_addr {
+                continue;
+            }
+            Self::send_to_slot(transport, message.clone(), slot).await?;
         }
         Ok(())
     }
 
     /// Static version of broadcast_message that can be used from timer tasks
     pub async fn broadcast_message_static(
-        socket: &Arc<UdpSocket>,
+        transport: &Arc<dyn Transport>,
         message: ServerMessage,
-        players: &std::collections::HashMap<std::net::SocketAddr, shared::Player>,
+        players: &mut [Option<PlayerSlot>],
     ) -> Result<(), Box<dyn std::error::Error>> {
-        let message_json = serde_json::to_string(&message)?;
-        for addr in players.keys() {
-            if let Err(e) = socket.send_to(message_json.as_bytes(), addr).await {
-                log::warn!("Failed to send message to {}: {}", addr, e);
+        for slot in players.iter_mut().flatten() {
+            if let Err(e) = Self::send_to_slot(transport, message.clone(), slot).await {
+                log::warn!("Failed to send message to {}: {}", slot.addr, e);
             }
         }
         Ok(())
@@ -1,9 +1,11 @@
-mod client_messages;
+pub mod game;
 mod game_state;
+pub mod handshake;
 mod message_handlers;
 mod message_helpers;
-mod server_messages;
+pub mod raycast;
+mod snapshot;
 
-use server_messages::*;
 pub mod server;
 pub use server::*;
+pub use snapshot::GameSnapshot;
@@ -0,0 +1,117 @@
+use shared::map::MazeMap;
+use shared::server::PlayerId;
+use shared::Position;
+
+use super::game::PlayerSlot;
+
+/// Radius used for a player's hit sphere, matching the visual size of the
+/// player model rendered client-side.
+pub const PLAYER_HIT_RADIUS: f32 = 0.5;
+
+/// Casts a ray from `origin` along the (assumed already normalized) `dir`
+/// through `map`'s x/y grid using a DDA traversal, returning the distance to
+/// the first solid wall cell (or the edge of the map, whichever comes
+/// first). Only the x/y components of `origin`/`dir` are used - this maze's
+/// geometry, and every player's movement, is confined to that plane. The
+/// traversal runs in cell space (`origin` divided by `map.tile_size`) so it
+/// works the same regardless of how large a world unit one tile is; the
+/// returned distance is scaled back to world units before returning.
+pub fn wall_distance(map: &MazeMap, origin: Position, dir: Position) -> f32 {
+    let tile_size = map.tile_size;
+    let (origin_x, origin_y) = (origin.x / tile_size, origin.y / tile_size);
+    let (dir_x, dir_y) = (dir.x, dir.y);
+
+    let mut map_x = origin_x.floor() as i32;
+    let mut map_y = origin_y.floor() as i32;
+
+    let delta_dist_x = if dir_x == 0.0 {
+        f32::INFINITY
+    } else {
+        (1.0 / dir_x).abs()
+    };
+    let delta_dist_y = if dir_y == 0.0 {
+        f32::INFINITY
+    } else {
+        (1.0 / dir_y).abs()
+    };
+
+    let (step_x, mut side_dist_x) = if dir_x < 0.0 {
+        (-1, (origin_x - map_x as f32) * delta_dist_x)
+    } else {
+        (1, (map_x as f32 + 1.0 - origin_x) * delta_dist_x)
+    };
+    let (step_y, mut side_dist_y) = if dir_y < 0.0 {
+        (-1, (origin_y - map_y as f32) * delta_dist_y)
+    } else {
+        (1, (map_y as f32 + 1.0 - origin_y) * delta_dist_y)
+    };
+
+    // A ray can cross at most one cell per grid line in each axis, so it can
+    // never need more steps than the maze's two dimensions combined to
+    // either hit a wall or walk off the edge.
+    for _ in 0..(map.width() + map.height()) {
+        let dist_to_boundary = if side_dist_x < side_dist_y {
+            let dist = side_dist_x;
+            side_dist_x += delta_dist_x;
+            map_x += step_x;
+            dist
+        } else {
+            let dist = side_dist_y;
+            side_dist_y += delta_dist_y;
+            map_y += step_y;
+            dist
+        };
+
+        if map_x < 0 || map_y < 0 || map_x as usize >= map.width() || map_y as usize >= map.height()
+        {
+            return dist_to_boundary * tile_size;
+        }
+        if map[map_y as usize][map_x as usize].is_wall() {
+            return dist_to_boundary * tile_size;
+        }
+    }
+
+    f32::INFINITY
+}
+
+/// Finds the nearest point `t >= 0` along `origin + t * dir` where the ray
+/// enters `center`'s hit sphere of `radius`, or `None` if it misses entirely.
+fn sphere_hit_distance(origin: Position, dir: Position, center: Position, radius: f32) -> Option<f32> {
+    let oc_x = origin.x - center.x;
+    let oc_y = origin.y - center.y;
+
+    // `dir` is normalized, so the quadratic's `a` coefficient is 1.
+    let b = 2.0 * (dir.x * oc_x + dir.y * oc_y);
+    let c = oc_x * oc_x + oc_y * oc_y - radius * radius;
+    let discriminant = b * b - 4.0 * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    let t = (-b - discriminant.sqrt()) / 2.0;
+    if t < 0.0 {
+        None
+    } else {
+        Some(t)
+    }
+}
+
+/// Among every player other than `shooter_id`, finds the closest one whose
+/// hit sphere the ray enters before it would hit a wall, if any.
+pub fn closest_player_hit(
+    shooter_id: PlayerId,
+    origin: Position,
+    dir: Position,
+    wall_distance: f32,
+    players: impl Iterator<Item = (PlayerId, &PlayerSlot)>,
+) -> Option<PlayerId> {
+    players
+        .filter(|(id, _)| *id != shooter_id)
+        .filter_map(|(id, slot)| {
+            sphere_hit_distance(origin, dir, slot.player.position, PLAYER_HIT_RADIUS)
+                .filter(|t| *t < wall_distance)
+                .map(|t| (id, t))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(id, _)| id)
+}
@@ -1,12 +1,19 @@
 use super::game::Game;
+use crate::transport::UdpTransport;
 
+use shared::channel::ChannelHeader;
 use shared::server::{ClientMessage, ServerMessage};
+use shared::transport::{DeliveryGuarantee, PeerId, Transport};
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::net::UdpSocket;
-use tokio::sync::Mutex;
+use tokio::sync::{oneshot, Mutex};
 use tokio::time::{self, Duration};
 
+/// How often the running match is snapshotted to `snapshot_path`, when set.
+const SNAPSHOT_INTERVAL_SECS: u64 = 10;
+
 #[derive(Debug)]
 /// Main game server handling network communication and game state
 ///
@@ -21,8 +28,12 @@ pub struct Server {
     port: u16,
     pub min_players: u8,
     pub max_players: u8,
+    pub heartbeat_timeout: Duration,
+    pub max_unacked_backlog: usize,
+    snapshot_path: Option<PathBuf>,
     game_state: Arc<Mutex<Game>>,
     game_start_timer: Option<Instant>,
+    shutdown: Option<oneshot::Receiver<()>>,
 }
 
 impl Server {
@@ -32,11 +43,27 @@ impl Server {
             port,
             min_players: 1,
             max_players: 10,
+            heartbeat_timeout: Duration::from_secs(10),
+            max_unacked_backlog: 256,
+            snapshot_path: None,
             game_state: Arc::new(Mutex::new(Game::new())),
             game_start_timer: None,
+            shutdown: None,
         }
     }
 
+    /// Registers a one-shot signal that makes `start()` return cleanly as soon
+    /// as it fires, instead of looping forever. Meant for in-process hosts
+    /// that need to tear the socket down on demand rather than leaking the
+    /// listening task.
+    ///
+    /// # Returns
+    /// Mutable Self for method chaining
+    pub fn shutdown_on(&mut self, signal: oneshot::Receiver<()>) -> &mut Self {
+        self.shutdown = Some(signal);
+        self
+    }
+
     /// Sets minimum required players to start a match
     ///
     /// # Arguments
@@ -61,7 +88,48 @@ impl Server {
         self
     }
 
-    /// Starts the game server and begins listening for UDP packets
+    /// Sets how long a player can go without sending any message before the
+    /// heartbeat reaper drops them and frees their slot
+    ///
+    /// # Arguments
+    /// * `timeout` - Idle duration after which a player is considered disconnected
+    ///
+    /// # Returns
+    /// Mutable Self for method chaining
+    pub fn heartbeat_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.heartbeat_timeout = timeout;
+        self
+    }
+
+    /// Sets how many reliable sends a client can leave unacked before the reaper
+    /// gives up on it and disconnects it, so one stalled peer can't grow its
+    /// backlog forever and wedge broadcasts
+    ///
+    /// # Arguments
+    /// * `max` - Maximum number of outstanding unacked reliable sends
+    ///
+    /// # Returns
+    /// Mutable Self for method chaining
+    pub fn max_unacked_backlog(&mut self, max: usize) -> &mut Self {
+        self.max_unacked_backlog = max;
+        self
+    }
+
+    /// Periodically saves the running match to `path` as a `GameSnapshot`, and
+    /// restores from it on the next `start()` if it exists - so a crashed
+    /// server picks the match back up instead of losing it outright.
+    ///
+    /// # Arguments
+    /// * `path` - File to save the match snapshot to and restore it from
+    ///
+    /// # Returns
+    /// Mutable Self for method chaining
+    pub fn snapshot_path<P: Into<PathBuf>>(&mut self, path: P) -> &mut Self {
+        self.snapshot_path = Some(path.into());
+        self
+    }
+
+    /// Starts the game server and begins listening for packets
     ///
     /// # Returns
     /// Result indicating success or failure
@@ -80,13 +148,43 @@ impl Server {
         let socket = UdpSocket::bind(&addr).await?;
         log::info!("Server started on {}", addr);
 
-        let socket = Arc::new(socket);
+        // The game logic only ever talks to a `dyn Transport`; swapping in
+        // `QuicTransport` (or any other impl) doesn't touch anything below this line.
+        let transport: Arc<dyn Transport> = Arc::new(UdpTransport::new(Arc::new(socket)));
         let game_state = self.game_state.clone();
 
+        if let Some(path) = &self.snapshot_path {
+            match Game::load_snapshot(path).await {
+                Ok(snapshot) => {
+                    game_state.lock().await.restore_from_snapshot(snapshot);
+                    log::info!("Resumed match from snapshot at {}", path.display());
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => log::warn!("Failed to load snapshot at {}: {}", path.display(), e),
+            }
+        }
+
+        // Periodically persist the running match so a crash doesn't lose it
+        if let Some(path) = self.snapshot_path.clone() {
+            let game_state_snapshot = game_state.clone();
+            tokio::spawn(async move {
+                let mut interval = time::interval(Duration::from_secs(SNAPSHOT_INTERVAL_SECS));
+                loop {
+                    interval.tick().await;
+                    let state = game_state_snapshot.lock().await;
+                    if let Err(e) = state.save_snapshot(&path).await {
+                        log::warn!("Failed to save snapshot to {}: {}", path.display(), e);
+                    }
+                }
+            });
+        }
+
         // Create a timer check task
         let game_state_timer = game_state.clone();
-        let socket_timer = socket.clone();
+        let transport_timer = transport.clone();
         let min_players = self.min_players;
+        let heartbeat_timeout = self.heartbeat_timeout;
+        let max_unacked_backlog = self.max_unacked_backlog;
 
         tokio::spawn(async move {
             let mut interval = time::interval(Duration::from_secs(1));
@@ -95,9 +193,81 @@ impl Server {
 
                 let mut state = game_state_timer.lock().await;
 
+                // Give up on handshakes nobody ever finished; otherwise a client
+                // that requests a challenge and vanishes holds its slot forever.
+                let handshake_timeout =
+                    Duration::from_secs(super::handshake::HANDSHAKE_TIMEOUT_SECS);
+                state
+                    .pending_handshakes
+                    .retain(|_, pending| pending.issued_at.elapsed() <= handshake_timeout);
+
+                // Resend reliable sends that haven't been acked yet, backing off
+                // exponentially between attempts. A player that never acks eventually
+                // gets reaped as idle by the heartbeat timeout below.
+                for slot in state.players.iter_mut().flatten() {
+                    let due = slot.channel.due_for_resend();
+                    for seq in due {
+                        let peer = PeerId(slot.addr.to_string());
+                        let Some(pending) = slot.channel.unacked.get_mut(&seq) else {
+                            continue;
+                        };
+                        pending.attempts += 1;
+                        pending.sent_at = Instant::now();
+                        for datagram in &pending.fragments {
+                            if let Err(e) = transport_timer
+                                .send(&peer, datagram, DeliveryGuarantee::Reliable)
+                                .await
+                            {
+                                log::warn!("Failed to resend seq {} to {}: {}", seq, peer, e);
+                            }
+                        }
+                    }
+                }
+
+                // Reap players who haven't been heard from in a while, or whose
+                // reliable backlog has grown so large they can only be wedging
+                // broadcasts for everyone else.
+                let to_reap: Vec<_> = state
+                    .iter()
+                    .filter_map(|(id, slot)| {
+                        if slot.player.last_seen.elapsed() > heartbeat_timeout {
+                            Some((id, slot.player.username.clone(), "timed out"))
+                        } else if slot.channel.unacked.len() > max_unacked_backlog {
+                            Some((id, slot.player.username.clone(), "fell too far behind on acks"))
+                        } else {
+                            None
+                        }
+                    })
+                    .collect();
+
+                for (player_id, username, reason) in to_reap {
+                    state.remove(player_id);
+                    log::info!("Player {} {} and was disconnected", username, reason);
+
+                    let message = ServerMessage::PlayerDisconnected { player_id };
+                    if let Err(e) = Self::broadcast_message_static(
+                        &transport_timer,
+                        message,
+                        &mut state.players,
+                    )
+                    .await
+                    {
+                        log::error!("Failed to broadcast player disconnect: {}", e);
+                    }
+
+                    // Cancel the start countdown if this drop pushes us below min_players
+                    if state.state == super::game::GameState::Waiting
+                        && (state.player_count() as u8) < min_players
+                        && state.game_start_time.is_some()
+                    {
+                        state.game_start_time = None;
+                        log::info!("Player count dropped below minimum after timeout. Cancelling countdown.");
+                    }
+                }
+
                 // Check if we have enough players and game is in waiting state
                 if state.state == super::game::GameState::Waiting {
-                    let player_count = state.players.len() as u8;
+                    let player_count = state.player_count() as u8;
 
                     // Set start timer if we have enough players and timer isn't set yet
                     if player_count >= min_players {
@@ -125,9 +295,9 @@ impl Server {
                                 maze_level: state.maze_level,
                             };
                             if let Err(e) = Self::broadcast_message_static(
-                                &socket_timer,
+                                &transport_timer,
                                 message,
-                                &state.players,
+                                &mut state.players,
                             )
                             .await
                             {
@@ -139,52 +309,166 @@ impl Server {
             }
         });
 
-        let mut buf = vec![0u8; 1024];
+        // Broadcast an authoritative world snapshot at a fixed rate so clients can
+        // reconcile their predicted movement against the server's view of the world.
+        let game_state_tick = game_state.clone();
+        let transport_tick = transport.clone();
+        tokio::spawn(async move {
+            let mut interval = time::interval(Duration::from_millis(50));
+            loop {
+                interval.tick().await;
+
+                let mut state = game_state_tick.lock().await;
+                if state.state != super::game::GameState::InProgress {
+                    continue;
+                }
+
+                state.tick = state.tick.wrapping_add(1);
+                let message = ServerMessage::WorldFrame {
+                    tick: state.tick,
+                    last_processed_input: state.last_processed_input.clone(),
+                    players: state
+                        .iter()
+                        .map(|(id, slot)| (id, slot.player.position, slot.player.rotation))
+                        .collect(),
+                };
+                if let Err(e) =
+                    Self::broadcast_message_static(&transport_tick, message, &mut state.players)
+                        .await
+                {
+                    log::error!("Failed to broadcast world frame: {}", e);
+                }
+            }
+        });
+
+        let mut shutdown = self.shutdown.take();
+
         loop {
             log::trace!("Waiting for incoming packets...");
-            let (len, addr) = socket.recv_from(&mut buf).await?;
-            let message = String::from_utf8_lossy(&buf[..len]);
+            let (bytes, peer) = match &mut shutdown {
+                Some(signal) => {
+                    tokio::select! {
+                        result = transport.recv() => result?,
+                        _ = signal => {
+                            log::info!("Shutdown signal received, stopping server");
+                            return Ok(());
+                        }
+                    }
+                }
+                None => transport.recv().await?,
+            };
+            let addr: std::net::SocketAddr = peer.0.parse()?;
 
-            log::trace!("Received message from {}: {}", addr, message);
+            let Some((header, payload)) = ChannelHeader::decode(&bytes) else {
+                log::warn!("Dropping datagram from {} with malformed channel header", addr);
+                continue;
+            };
 
-            let client_message = serde_json::from_str::<ClientMessage>(&message);
+            // Feed the sender's channel state: retires acked sends, reassembles
+            // fragments, and (on the reliable channel) buffers this behind any
+            // gap until it's next in line. Unregistered peers (mid-handshake)
+            // have no slot yet, so their datagrams are assumed whole and unordered.
+            let ready_payloads: Vec<Vec<u8>> = {
+                let mut state = game_state.lock().await;
+                if let Some(player_id) = state.id_for_addr(&addr) {
+                    let slot = state.get_mut(player_id).unwrap();
+                    slot.channel.receive(&header, payload);
+                    std::iter::from_fn(|| slot.channel.pop_ready()).collect()
+                } else {
+                    vec![payload.to_vec()]
+                }
+            };
 
-            if let Err(e) = client_message {
-                log::warn!("Failed to parse client message: {}", e);
+            for payload in ready_payloads {
+                log::trace!("Received message from {}", addr);
 
-                let error_message = ServerMessage::Error {
-                    message: format!("Bad Payload: {}", e),
-                };
+                let client_message = bincode::deserialize::<ClientMessage>(&payload);
 
-                self.send_message(&socket, error_message, &addr).await?;
-                continue;
-            }
+                if let Err(e) = client_message {
+                    log::warn!("Failed to parse client message: {}", e);
 
-            let client_message = client_message.unwrap();
+                    let error_message = ServerMessage::Error {
+                        message: format!("Bad Payload: {}", e),
+                    };
 
-            match client_message {
-                ClientMessage::JoinGame { username } => {
-                    self.handle_join_game(game_state.clone(), socket.clone(), addr, username)
-                        .await?;
+                    self.send_message(&transport, error_message, &peer).await?;
+                    continue;
                 }
-                ClientMessage::Move {
-                    position,
-                    rotation,
-                    yield_control,
-                } => {
-                    self.handle_move(
-                        game_state.clone(),
-                        socket.clone(),
-                        addr,
+
+                let client_message = client_message.unwrap();
+
+                match client_message {
+                    ClientMessage::Hello { protocol_version } => {
+                        self.handle_hello(transport.clone(), addr, protocol_version)
+                            .await?;
+                    }
+                    ClientMessage::JoinGame {
+                        username,
+                        protocol_version,
+                    } => {
+                        self.handle_join_game(
+                            game_state.clone(),
+                            transport.clone(),
+                            addr,
+                            username,
+                            protocol_version,
+                        )
+                        .await?;
+                    }
+                    ClientMessage::Connect {
+                        pubkey,
+                        nonce,
+                        signature,
+                    } => {
+                        self.handle_connect(
+                            game_state.clone(),
+                            transport.clone(),
+                            addr,
+                            pubkey,
+                            nonce,
+                            signature,
+                        )
+                        .await?;
+                    }
+                    ClientMessage::Move {
                         position,
                         rotation,
                         yield_control,
-                    )
-                    .await?;
-                }
-                ClientMessage::ShotPlayer { player_username } => {
-                    self.handle_shoot(game_state.clone(), socket.clone(), addr, player_username)
+                        input_sequence,
+                    } => {
+                        self.handle_move(
+                            game_state.clone(),
+                            transport.clone(),
+                            addr,
+                            position,
+                            rotation,
+                            yield_control,
+                            input_sequence,
+                        )
                         .await?;
+                    }
+                    ClientMessage::ShotPlayer { origin, direction } => {
+                        self.handle_shoot(game_state.clone(), transport.clone(), addr, origin, direction)
+                            .await?;
+                    }
+                    ClientMessage::Heartbeat => {
+                        let mut state = game_state.lock().await;
+                        if let Some(player_id) = state.id_for_addr(&addr) {
+                            state.get_mut(player_id).unwrap().player.touch();
+                        }
+                    }
+                    ClientMessage::Ping { client_time } => {
+                        self.handle_ping(game_state.clone(), transport.clone(), addr, client_time)
+                            .await?;
+                    }
+                    ClientMessage::SwitchWeapon { index } => {
+                        self.handle_switch_weapon(game_state.clone(), transport.clone(), addr, index)
+                            .await?;
+                    }
+                    ClientMessage::Reload => {
+                        self.handle_reload(game_state.clone(), transport.clone(), addr)
+                            .await?;
+                    }
                 }
             }
         }
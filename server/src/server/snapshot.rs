@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+use shared::Player;
+use std::io;
+use std::path::Path;
+
+use super::game::{Game, GameState};
+
+/// Serializable picture of an in-progress match: every connected `Player`'s
+/// state plus the match metadata needed to pick up where it left off. Written
+/// to disk so a crashed server can resume a match instead of losing it
+/// outright, and the basis for `ServerMessage::GameSnapshot` sent to a client
+/// that connects mid-game.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GameSnapshot {
+    pub players: Vec<Player>,
+    pub state: GameState,
+    pub maze_level: u8,
+    pub tick: u32,
+}
+
+impl Game {
+    /// Captures the current match as a `GameSnapshot`.
+    pub fn snapshot(&self) -> GameSnapshot {
+        GameSnapshot {
+            players: self.iter().map(|(_, slot)| slot.player.clone()).collect(),
+            state: self.state,
+            maze_level: self.maze_level,
+            tick: self.tick,
+        }
+    }
+
+    /// Writes `self.snapshot()` to `path` as JSON, overwriting whatever was
+    /// there before.
+    pub async fn save_snapshot(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_vec_pretty(&self.snapshot())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        tokio::fs::write(path, json).await
+    }
+
+    /// Reads back a `GameSnapshot` previously written by `save_snapshot`.
+    pub async fn load_snapshot(path: &Path) -> io::Result<GameSnapshot> {
+        let bytes = tokio::fs::read(path).await?;
+        serde_json::from_slice(&bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Restores match metadata from a loaded snapshot and stashes each
+    /// player's last known state in `restorable_players`, keyed by username,
+    /// for `handle_connect` to consume as reconnecting players rejoin. No
+    /// `PlayerSlot`s are created here - a slot needs a live `addr` and
+    /// `ChannelState`, which only exist once a player actually reconnects.
+    pub fn restore_from_snapshot(&mut self, snapshot: GameSnapshot) {
+        self.state = snapshot.state;
+        self.maze_level = snapshot.maze_level;
+        self.maze = shared::map::generate_procedural_maze(
+            shared::map::MAZE_WIDTH,
+            shared::map::MAZE_HEIGHT,
+            snapshot.maze_level as u64,
+        )
+        .map;
+        self.tick = snapshot.tick;
+        self.restorable_players = snapshot
+            .players
+            .into_iter()
+            .map(|player| (player.username.clone(), player))
+            .collect();
+    }
+}
@@ -0,0 +1,6 @@
+mod quic;
+mod udp;
+
+#[cfg(feature = "quic-transport")]
+pub use quic::QuicTransport;
+pub use udp::UdpTransport;
@@ -0,0 +1,99 @@
+//! QUIC/iroh-net transport, giving lobbies NAT-traversed peer connections and a
+//! matchmaker-style rendezvous instead of requiring a public IP for the
+//! server — the same approach fishfolk's bones socket layer takes. Gated
+//! behind the `quic-transport` feature until `iroh-net` is added to this
+//! workspace's manifest.
+#![cfg(feature = "quic-transport")]
+
+use async_trait::async_trait;
+use iroh_net::{endpoint::Connection, Endpoint, NodeId};
+use shared::transport::{DeliveryGuarantee, PeerId, Transport};
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+
+/// ALPN identifying this game's protocol during the iroh-net handshake.
+const ALPN: &[u8] = b"maze-wars/1";
+
+/// Reliable messages go out on an ordered uni-directional QUIC stream;
+/// unreliable ones use iroh-net's unreliable datagram extension, so `Move`
+/// updates never wait behind a `GameStart` retransmit.
+pub struct QuicTransport {
+    endpoint: Endpoint,
+    connections: Mutex<HashMap<NodeId, Connection>>,
+}
+
+impl QuicTransport {
+    /// Binds an iroh-net endpoint and registers with the relay-assisted
+    /// rendezvous so peers behind NAT can still find and connect to us.
+    pub async fn bind() -> anyhow::Result<Self> {
+        let endpoint = Endpoint::builder()
+            .alpns(vec![ALPN.to_vec()])
+            .bind()
+            .await?;
+        Ok(Self {
+            endpoint,
+            connections: Mutex::new(HashMap::new()),
+        })
+    }
+
+    async fn connection_for(&self, peer: &PeerId) -> std::io::Result<Connection> {
+        let node_id: NodeId = peer
+            .0
+            .parse()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+
+        if let Some(conn) = self.connections.lock().await.get(&node_id) {
+            return Ok(conn.clone());
+        }
+
+        let conn = self
+            .endpoint
+            .connect(node_id, ALPN)
+            .await
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::ConnectionRefused, e))?;
+        self.connections.lock().await.insert(node_id, conn.clone());
+        Ok(conn)
+    }
+}
+
+#[async_trait]
+impl Transport for QuicTransport {
+    async fn send(
+        &self,
+        peer: &PeerId,
+        bytes: &[u8],
+        guarantee: DeliveryGuarantee,
+    ) -> std::io::Result<()> {
+        let conn = self.connection_for(peer).await?;
+        match guarantee {
+            DeliveryGuarantee::Reliable => {
+                let mut stream = conn
+                    .open_uni()
+                    .await
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+                stream
+                    .write_all(bytes)
+                    .await
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+                stream
+                    .finish()
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+            }
+            DeliveryGuarantee::Unreliable => {
+                conn.send_datagram(bytes.to_vec().into())
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn recv(&self) -> std::io::Result<(Vec<u8>, PeerId)> {
+        // Accepting inbound connections and multiplexing their streams and
+        // datagrams into a single queue is future work for the matchmaker
+        // rendezvous; this transport is send-only for now.
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "QuicTransport::recv is not implemented yet",
+        ))
+    }
+}
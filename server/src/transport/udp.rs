@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+use shared::transport::{DeliveryGuarantee, PeerId, Transport};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::UdpSocket;
+
+/// The transport shipped today: raw UDP datagrams carrying a `shared::channel`
+/// header followed by a `bincode`-encoded message. Every send is best-effort
+/// regardless of the requested `DeliveryGuarantee` — actual retransmission for
+/// `Reliable` sends is layered on top by the caller (see `ChannelState` and the
+/// resend task in `Server::start`) rather than handled by this transport itself,
+/// unlike `QuicTransport`.
+pub struct UdpTransport {
+    socket: Arc<UdpSocket>,
+}
+
+impl UdpTransport {
+    pub fn new(socket: Arc<UdpSocket>) -> Self {
+        Self { socket }
+    }
+}
+
+#[async_trait]
+impl Transport for UdpTransport {
+    async fn send(
+        &self,
+        peer: &PeerId,
+        bytes: &[u8],
+        _guarantee: DeliveryGuarantee,
+    ) -> std::io::Result<()> {
+        let addr: SocketAddr = peer
+            .0
+            .parse()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        self.socket.send_to(bytes, addr).await?;
+        Ok(())
+    }
+
+    async fn recv(&self) -> std::io::Result<(Vec<u8>, PeerId)> {
+        let mut buf = vec![0u8; 2048];
+        let (len, addr) = self.socket.recv_from(&mut buf).await?;
+        buf.truncate(len);
+        Ok((buf, PeerId(addr.to_string())))
+    }
+}
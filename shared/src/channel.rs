@@ -0,0 +1,355 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+/// Fixed-size header prepended to every datagram, ahead of the `bincode`-encoded
+/// message payload
+pub const HEADER_LEN: usize = 11;
+
+/// Largest a single datagram (header + body) is allowed to get before `build_fragments`
+/// splits it up, comfortably under a typical internet path MTU.
+pub const MAX_DATAGRAM_LEN: usize = 1200;
+
+/// Which delivery semantics a datagram travels under
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum ChannelId {
+    /// Ordered, resent on a backoff until acked
+    Reliable = 0,
+    /// Fire-and-forget; a stale or duplicate packet is simply dropped on arrival
+    Unreliable = 1,
+}
+
+impl ChannelId {
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(ChannelId::Reliable),
+            1 => Some(ChannelId::Unreliable),
+            _ => None,
+        }
+    }
+}
+
+/// Per-datagram header: which channel it's on, its sequence number on that
+/// channel, a sliding ack window (`ack` plus the 32 reliable seqs before it)
+/// acknowledging what the sender has received from us so far, and this
+/// datagram's place among the fragments `build_fragments` split its message
+/// into (`frag_count == 1` for the overwhelmingly common unfragmented case).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelHeader {
+    pub channel: ChannelId,
+    pub seq: u16,
+    pub ack: u16,
+    pub ack_bitfield: u32,
+    pub frag_index: u8,
+    pub frag_count: u8,
+}
+
+impl ChannelHeader {
+    pub fn encode(&self) -> [u8; HEADER_LEN] {
+        let mut buf = [0u8; HEADER_LEN];
+        buf[0] = self.channel as u8;
+        buf[1..3].copy_from_slice(&self.seq.to_be_bytes());
+        buf[3..5].copy_from_slice(&self.ack.to_be_bytes());
+        buf[5..9].copy_from_slice(&self.ack_bitfield.to_be_bytes());
+        buf[9] = self.frag_index;
+        buf[10] = self.frag_count;
+        buf
+    }
+
+    /// Parses the header off the front of `bytes`, returning it along with the
+    /// remaining payload. `None` if `bytes` is too short or names an unknown channel.
+    pub fn decode(bytes: &[u8]) -> Option<(Self, &[u8])> {
+        if bytes.len() < HEADER_LEN {
+            return None;
+        }
+        let channel = ChannelId::from_byte(bytes[0])?;
+        let seq = u16::from_be_bytes([bytes[1], bytes[2]]);
+        let ack = u16::from_be_bytes([bytes[3], bytes[4]]);
+        let ack_bitfield = u32::from_be_bytes([bytes[5], bytes[6], bytes[7], bytes[8]]);
+        let frag_index = bytes[9];
+        let frag_count = bytes[10];
+        Some((
+            ChannelHeader {
+                channel,
+                seq,
+                ack,
+                ack_bitfield,
+                frag_index,
+                frag_count,
+            },
+            &bytes[HEADER_LEN..],
+        ))
+    }
+}
+
+/// Splits `body` into one or more datagrams (each already `ChannelHeader`-prefixed
+/// and ready to hand to a socket) no larger than `MAX_DATAGRAM_LEN`, so a payload
+/// too big for one UDP packet still arrives - the receiver reassembles them by
+/// `frag_index` via `ChannelState::receive` before decoding. Almost always
+/// returns a single datagram; only splits when `body` actually needs it.
+pub fn build_fragments(channel: ChannelId, seq: u16, ack: u16, ack_bitfield: u32, body: &[u8]) -> Vec<Vec<u8>> {
+    let max_body = MAX_DATAGRAM_LEN - HEADER_LEN;
+    let chunks: Vec<&[u8]> = if body.len() <= max_body {
+        vec![body]
+    } else {
+        body.chunks(max_body).collect()
+    };
+    let frag_count = chunks.len() as u8;
+
+    chunks
+        .into_iter()
+        .enumerate()
+        .map(|(frag_index, chunk)| {
+            let header = ChannelHeader {
+                channel,
+                seq,
+                ack,
+                ack_bitfield,
+                frag_index: frag_index as u8,
+                frag_count,
+            };
+            let mut datagram = header.encode().to_vec();
+            datagram.extend_from_slice(chunk);
+            datagram
+        })
+        .collect()
+}
+
+/// A reliable send we haven't heard an ack for yet. Holds every fragment
+/// `build_fragments` split it into - one entry for the unfragmented case - so a
+/// resend replays the whole logical message, not just part of it.
+#[derive(Debug, Clone)]
+pub struct UnackedSend {
+    pub fragments: Vec<Vec<u8>>,
+    pub sent_at: Instant,
+    pub attempts: u32,
+}
+
+/// In-progress reassembly of a fragmented message, indexed by the fragment
+/// slots `build_fragments` assigned it.
+#[derive(Debug)]
+struct FragmentAssembly {
+    parts: Vec<Option<Vec<u8>>>,
+}
+
+impl FragmentAssembly {
+    fn new(count: u8) -> Self {
+        Self {
+            parts: vec![None; count as usize],
+        }
+    }
+
+    /// Records one fragment's bytes. Returns the reassembled payload once every
+    /// slot has arrived.
+    fn insert(&mut self, index: u8, bytes: &[u8]) -> Option<Vec<u8>> {
+        if let Some(slot) = self.parts.get_mut(index as usize) {
+            *slot = Some(bytes.to_vec());
+        }
+        if self.parts.iter().all(Option::is_some) {
+            let mut full = Vec::new();
+            for part in &self.parts {
+                full.extend_from_slice(part.as_ref().unwrap());
+            }
+            Some(full)
+        } else {
+            None
+        }
+    }
+}
+
+/// Per-peer channel bookkeeping, identical on both ends of a connection: which
+/// sequence number comes next on each channel, what we've seen from the peer's
+/// reliable channel so far (to build our outgoing ack and to reject duplicate
+/// resends/stale unreliable packets), our own unacked reliable sends, and the
+/// reassembly/reordering state for what we've received.
+#[derive(Debug, Default)]
+pub struct ChannelState {
+    next_reliable_seq: u16,
+    next_unreliable_seq: u16,
+    highest_reliable_received: Option<u16>,
+    reliable_received_bitfield: u32,
+    highest_unreliable_received: Option<u16>,
+    pub unacked: HashMap<u16, UnackedSend>,
+    fragments: HashMap<(ChannelId, u16), FragmentAssembly>,
+    reliable_reorder: BTreeMap<u16, Vec<u8>>,
+    /// Next reliable sequence number we're waiting to release, in delivery
+    /// order. Both ends' `next_reliable_seq` starts at 0, so this can start
+    /// there too rather than being seeded from whichever reliable datagram
+    /// happens to be *processed* first - which, over UDP, is not necessarily
+    /// the one with the lowest `seq`.
+    next_reliable_to_release: u16,
+    ready: VecDeque<Vec<u8>>,
+}
+
+impl ChannelState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allocates and advances the next sequence number for `channel`
+    pub fn next_seq(&mut self, channel: ChannelId) -> u16 {
+        let counter = match channel {
+            ChannelId::Reliable => &mut self.next_reliable_seq,
+            ChannelId::Unreliable => &mut self.next_unreliable_seq,
+        };
+        let seq = *counter;
+        *counter = counter.wrapping_add(1);
+        seq
+    }
+
+    /// The `ack`/`ack_bitfield` pair to stamp on our next outgoing header,
+    /// describing what we've received from the peer on the reliable channel
+    pub fn outgoing_ack(&self) -> (u16, u32) {
+        (
+            self.highest_reliable_received.unwrap_or(0),
+            self.reliable_received_bitfield,
+        )
+    }
+
+    /// Retires any of our unacked reliable sends that `header`'s ack window
+    /// confirms the peer has received. Applied on every arriving datagram,
+    /// including individual fragments of a still-incomplete message, since the
+    /// ack window describes the peer's receipt of *our* sends and has nothing
+    /// to do with whether this particular datagram is itself complete yet.
+    fn retire_acked(&mut self, header: &ChannelHeader) {
+        self.unacked
+            .retain(|seq, _| !is_acked(*seq, header.ack, header.ack_bitfield));
+    }
+
+    /// Dedupes and tracks a fully-reassembled message's sequence number,
+    /// updating what we've seen of the peer's channels. Returns `false` if this
+    /// exact message should be discarded - a reliable message we've already
+    /// processed (a resend), or a stale/duplicate unreliable one.
+    fn observe_incoming(&mut self, header: &ChannelHeader) -> bool {
+        match header.channel {
+            ChannelId::Reliable => match self.highest_reliable_received {
+                None => {
+                    self.highest_reliable_received = Some(header.seq);
+                    true
+                }
+                Some(highest) if sequence_greater(header.seq, highest) => {
+                    let shift = header.seq.wrapping_sub(highest);
+                    self.reliable_received_bitfield = if shift > 31 {
+                        0
+                    } else {
+                        (self.reliable_received_bitfield << shift) | (1 << (shift - 1))
+                    };
+                    self.highest_reliable_received = Some(header.seq);
+                    true
+                }
+                Some(highest) if header.seq == highest => false,
+                Some(highest) => {
+                    let distance = highest.wrapping_sub(header.seq);
+                    if distance > 32 {
+                        false
+                    } else {
+                        let bit = 1 << (distance - 1);
+                        let already_seen = self.reliable_received_bitfield & bit != 0;
+                        self.reliable_received_bitfield |= bit;
+                        !already_seen
+                    }
+                }
+            },
+            ChannelId::Unreliable => match self.highest_unreliable_received {
+                Some(highest) if !sequence_greater(header.seq, highest) => false,
+                _ => {
+                    self.highest_unreliable_received = Some(header.seq);
+                    true
+                }
+            },
+        }
+    }
+
+    /// Feeds one fragment into the in-progress reassembly for its message.
+    /// Returns the full payload once every fragment has arrived, or
+    /// immediately for the unfragmented case (`frag_count <= 1`).
+    fn reassemble(&mut self, header: &ChannelHeader, payload: &[u8]) -> Option<Vec<u8>> {
+        if header.frag_count <= 1 {
+            return Some(payload.to_vec());
+        }
+        let key = (header.channel, header.seq);
+        let assembly = self
+            .fragments
+            .entry(key)
+            .or_insert_with(|| FragmentAssembly::new(header.frag_count));
+        let complete = assembly.insert(header.frag_index, payload);
+        if complete.is_some() {
+            self.fragments.remove(&key);
+        }
+        complete
+    }
+
+    /// Buffers a de-duplicated reliable payload and returns every payload now
+    /// ready to deliver in order: just `payload` if it's the next one expected,
+    /// plus any further payloads its arrival happened to complete a run of -
+    /// or nothing, if the gap it's waiting behind is still open.
+    fn release_in_order(&mut self, seq: u16, payload: Vec<u8>) -> Vec<Vec<u8>> {
+        self.reliable_reorder.insert(seq, payload);
+
+        let mut ready = Vec::new();
+        while let Some(payload) = self.reliable_reorder.remove(&self.next_reliable_to_release) {
+            ready.push(payload);
+            self.next_reliable_to_release = self.next_reliable_to_release.wrapping_add(1);
+        }
+        ready
+    }
+
+    /// Processes one incoming datagram end to end: retires acks, reassembles
+    /// fragments, and - for the reliable channel - buffers it until every
+    /// message ahead of it has been released. Anything now ready to deliver is
+    /// pushed onto the ready queue for `pop_ready` to drain, in delivery order.
+    pub fn receive(&mut self, header: &ChannelHeader, payload: &[u8]) {
+        self.retire_acked(header);
+
+        let Some(full_payload) = self.reassemble(header, payload) else {
+            return;
+        };
+        if !self.observe_incoming(header) {
+            return;
+        }
+
+        match header.channel {
+            ChannelId::Unreliable => self.ready.push_back(full_payload),
+            ChannelId::Reliable => {
+                for payload in self.release_in_order(header.seq, full_payload) {
+                    self.ready.push_back(payload);
+                }
+            }
+        }
+    }
+
+    /// Pops the next payload `receive` has made ready for delivery, in order.
+    pub fn pop_ready(&mut self) -> Option<Vec<u8>> {
+        self.ready.pop_front()
+    }
+
+    /// Seqs of unacked reliable sends whose resend backoff has elapsed
+    pub fn due_for_resend(&self) -> Vec<u16> {
+        self.unacked
+            .iter()
+            .filter(|(_, pending)| pending.sent_at.elapsed() >= resend_backoff(pending.attempts))
+            .map(|(seq, _)| *seq)
+            .collect()
+    }
+}
+
+/// Whether `seq` is covered by an `ack`/`ack_bitfield` pair
+fn is_acked(seq: u16, ack: u16, ack_bitfield: u32) -> bool {
+    if seq == ack {
+        return true;
+    }
+    let distance = ack.wrapping_sub(seq);
+    distance >= 1 && distance <= 32 && (ack_bitfield & (1 << (distance - 1))) != 0
+}
+
+/// Wrapping sequence comparison (RFC 1982 style): is `a` newer than `b`?
+fn sequence_greater(a: u16, b: u16) -> bool {
+    let diff = a.wrapping_sub(b);
+    diff != 0 && diff < 0x8000
+}
+
+/// Backoff before a reliable send's next resend attempt, doubling per attempt
+/// and capped at 30 seconds
+fn resend_backoff(attempts: u32) -> Duration {
+    Duration::from_secs((1u64 << attempts.min(5)).min(30))
+}
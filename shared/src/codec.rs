@@ -0,0 +1,224 @@
+//! Compact binary primitives backing `ClientMessage::encode`/`decode` and
+//! `ServerMessage::encode`/`decode`: varints for lengths, and centimeter/angle
+//! quantization for `Position`/`Rotation` so a `Move` packs into a fraction of
+//! what a generic serde codec would produce. Fixed-width integers are big-endian
+//! (varints are LEB128, least significant group first), and everything is
+//! position-independent - encoders append to an output `Vec<u8>`, decoders read
+//! from a byte slice starting at a cursor they advance themselves.
+
+use std::time::Instant;
+
+use crate::{rotation::Rotation, weapon::Weapon, Player, Position};
+
+/// Writes `value` as a LEB128-style varint: 7 payload bits per byte, with the
+/// high bit set on every byte but the last. At most 5 bytes for any `u32`.
+pub fn write_varint(mut value: u32, out: &mut Vec<u8>) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value != 0 {
+            out.push(byte | 0x80);
+        } else {
+            out.push(byte);
+            return;
+        }
+    }
+}
+
+/// Reads a varint written by `write_varint` starting at `*pos`, advancing
+/// `*pos` past it. `None` if the buffer ends mid-varint or the value would
+/// overflow a `u32`.
+pub fn read_varint(bytes: &[u8], pos: &mut usize) -> Option<u32> {
+    let mut value: u32 = 0;
+    for shift in (0..35).step_by(7) {
+        let byte = *bytes.get(*pos)?;
+        *pos += 1;
+        // Only the low 4 bits of a fifth byte still fit in a `u32`.
+        if shift == 28 && byte & 0x70 != 0 {
+            return None;
+        }
+        value |= ((byte & 0x7F) as u32) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// Wire units (centimeters) per game-world unit: a position component becomes
+/// an `i16` scaled by this before rounding.
+const POSITION_SCALE: f32 = 100.0;
+
+/// Packs one position component as a centimeter-scaled `i16`, clamped to its
+/// range rather than wrapping on overflow.
+pub fn quantize_coord(value: f32) -> i16 {
+    (value * POSITION_SCALE).round().clamp(i16::MIN as f32, i16::MAX as f32) as i16
+}
+
+pub fn dequantize_coord(value: i16) -> f32 {
+    value as f32 / POSITION_SCALE
+}
+
+pub fn write_position(position: &Position, out: &mut Vec<u8>) {
+    out.extend_from_slice(&quantize_coord(position.x).to_be_bytes());
+    out.extend_from_slice(&quantize_coord(position.y).to_be_bytes());
+    out.extend_from_slice(&quantize_coord(position.z).to_be_bytes());
+}
+
+pub fn read_position(bytes: &[u8], pos: &mut usize) -> Option<Position> {
+    Some(Position {
+        x: dequantize_coord(read_i16(bytes, pos)?),
+        y: dequantize_coord(read_i16(bytes, pos)?),
+        z: dequantize_coord(read_i16(bytes, pos)?),
+    })
+}
+
+/// Packs one angle - wrapped into `[0, 2π)` first - as a `u16` spanning the
+/// full range, about 0.1 milliradians of resolution.
+pub fn quantize_angle(radians: f32) -> u16 {
+    let wrapped = radians.rem_euclid(std::f32::consts::TAU);
+    ((wrapped / std::f32::consts::TAU) * u16::MAX as f32).round() as u16
+}
+
+pub fn dequantize_angle(value: u16) -> f32 {
+    (value as f32 / u16::MAX as f32) * std::f32::consts::TAU
+}
+
+pub fn write_rotation(rotation: &Rotation, out: &mut Vec<u8>) {
+    out.extend_from_slice(&quantize_angle(rotation.pitch).to_be_bytes());
+    out.extend_from_slice(&quantize_angle(rotation.yaw).to_be_bytes());
+    out.extend_from_slice(&quantize_angle(rotation.roll).to_be_bytes());
+}
+
+pub fn read_rotation(bytes: &[u8], pos: &mut usize) -> Option<Rotation> {
+    Some(Rotation {
+        pitch: dequantize_angle(read_u16(bytes, pos)?),
+        yaw: dequantize_angle(read_u16(bytes, pos)?),
+        roll: dequantize_angle(read_u16(bytes, pos)?),
+    })
+}
+
+pub fn write_string(value: &str, out: &mut Vec<u8>) {
+    write_varint(value.len() as u32, out);
+    out.extend_from_slice(value.as_bytes());
+}
+
+pub fn read_string(bytes: &[u8], pos: &mut usize) -> Option<String> {
+    let len = read_varint(bytes, pos)? as usize;
+    let slice = bytes.get(*pos..*pos + len)?;
+    *pos += len;
+    String::from_utf8(slice.to_vec()).ok()
+}
+
+/// Clamps a length read from an attacker-controlled varint (up to ~4 billion)
+/// down to what `bytes` could actually still hold from `pos` on - every
+/// encoded element takes at least one byte, so a count claiming more entries
+/// than remaining bytes is necessarily truncated or forged. Use this to size
+/// a `Vec`/`HashMap::with_capacity` before a decode loop, instead of trusting
+/// the count directly and pre-allocating gigabytes for a handful of reads
+/// that would fail anyway.
+pub fn capped_len(count: u32, bytes: &[u8], pos: usize) -> usize {
+    (count as usize).min(bytes.len().saturating_sub(pos))
+}
+
+pub fn read_array<const N: usize>(bytes: &[u8], pos: &mut usize) -> Option<[u8; N]> {
+    let slice = bytes.get(*pos..*pos + N)?;
+    *pos += N;
+    slice.try_into().ok()
+}
+
+pub fn read_i16(bytes: &[u8], pos: &mut usize) -> Option<i16> {
+    Some(i16::from_be_bytes(read_array::<2>(bytes, pos)?))
+}
+
+pub fn read_u16(bytes: &[u8], pos: &mut usize) -> Option<u16> {
+    Some(u16::from_be_bytes(read_array::<2>(bytes, pos)?))
+}
+
+pub fn write_u32(value: u32, out: &mut Vec<u8>) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
+
+pub fn read_u32(bytes: &[u8], pos: &mut usize) -> Option<u32> {
+    Some(u32::from_be_bytes(read_array::<4>(bytes, pos)?))
+}
+
+pub fn write_u64(value: u64, out: &mut Vec<u8>) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
+
+pub fn read_u64(bytes: &[u8], pos: &mut usize) -> Option<u64> {
+    Some(u64::from_be_bytes(read_array::<8>(bytes, pos)?))
+}
+
+pub fn write_f32(value: f32, out: &mut Vec<u8>) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
+
+pub fn read_f32(bytes: &[u8], pos: &mut usize) -> Option<f32> {
+    Some(f32::from_be_bytes(read_array::<4>(bytes, pos)?))
+}
+
+pub fn write_weapon(weapon: &Weapon, out: &mut Vec<u8>) {
+    write_string(&weapon.name, out);
+    write_u32(weapon.damage, out);
+    write_f32(weapon.fire_rate, out);
+    write_u32(weapon.ammo_count, out);
+    write_u32(weapon.magazine_size, out);
+    write_f32(weapon.range, out);
+    write_f32(weapon.reload_time, out);
+    write_f32(weapon.weight, out);
+}
+
+pub fn read_weapon(bytes: &[u8], pos: &mut usize) -> Option<Weapon> {
+    Some(Weapon {
+        name: read_string(bytes, pos)?,
+        damage: read_u32(bytes, pos)?,
+        fire_rate: read_f32(bytes, pos)?,
+        ammo_count: read_u32(bytes, pos)?,
+        magazine_size: read_u32(bytes, pos)?,
+        range: read_f32(bytes, pos)?,
+        reload_time: read_f32(bytes, pos)?,
+        weight: read_f32(bytes, pos)?,
+    })
+}
+
+/// Packs a full `Player` - used by `ServerMessage::GameSnapshot`, which is rare
+/// enough (sent once to a client joining mid-game) that shipping every field
+/// still beats the bandwidth of restating it per-field like `PlayerMove` does.
+/// `last_seen` isn't part of the wire shape - like its `#[serde(skip)]` bincode
+/// counterpart, it's reset to "now" on the receiving end.
+pub fn write_player(player: &Player, out: &mut Vec<u8>) {
+    write_string(&player.username, out);
+    write_position(&player.position, out);
+    write_u32(player.height, out);
+    write_rotation(&player.rotation, out);
+    write_u32(player.health, out);
+    write_varint(player.inventory.len() as u32, out);
+    for weapon in &player.inventory {
+        write_weapon(weapon, out);
+    }
+    write_varint(player.equipped as u32, out);
+    out.extend_from_slice(&player.pubkey);
+}
+
+pub fn read_player(bytes: &[u8], pos: &mut usize) -> Option<Player> {
+    Some(Player {
+        username: read_string(bytes, pos)?,
+        position: read_position(bytes, pos)?,
+        height: read_u32(bytes, pos)?,
+        rotation: read_rotation(bytes, pos)?,
+        health: read_u32(bytes, pos)?,
+        inventory: {
+            let count = read_varint(bytes, pos)?;
+            let mut inventory = Vec::with_capacity(capped_len(count, bytes, *pos));
+            for _ in 0..count {
+                inventory.push(read_weapon(bytes, pos)?);
+            }
+            inventory
+        },
+        equipped: read_varint(bytes, pos)? as usize,
+        pubkey: read_array::<32>(bytes, pos)?,
+        last_seen: Instant::now(),
+    })
+}
@@ -1,7 +1,11 @@
+pub mod channel;
+pub mod codec;
+pub mod map;
 mod player;
 mod position;
 mod rotation;
 pub mod server;
+pub mod transport;
 mod weapon;
 
 pub use player::Player;
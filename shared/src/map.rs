@@ -0,0 +1,252 @@
+//! Procedurally generated maze layouts, shared so the server can raycast
+//! shots against the exact same walls the client renders.
+
+use std::collections::VecDeque;
+use std::ops::{Index, IndexMut};
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+/// Default dimensions passed to `generate_procedural_maze`. Not baked into
+/// `MazeMap` itself - the server is free to generate a differently sized maze
+/// and every caller sizes itself off the map instance, not these constants.
+pub const MAZE_WIDTH: usize = 20;
+pub const MAZE_HEIGHT: usize = 15;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tile {
+    /// A solid wall cell. Carries a texture id so different maze regions can
+    /// use different wall art; `0` is the default/fallback texture.
+    Wall(u8),
+    Floor,
+}
+
+impl Tile {
+    pub fn is_wall(&self) -> bool {
+        matches!(self, Tile::Wall(_))
+    }
+}
+
+/// A maze's tile grid plus the world-space size of one tile. Owns its own
+/// `width`/`height` instead of baking them into the type, so the renderer
+/// and collision checks work the same for any map size the server ships.
+#[derive(Debug, Clone)]
+pub struct MazeMap {
+    /// World units spanned by one tile edge. `cast_ray`, `wall_distance` and
+    /// the minimap all convert through this instead of assuming a tile is
+    /// exactly `1.0` world unit wide.
+    pub tile_size: f32,
+    rows: Vec<Vec<Tile>>,
+}
+
+impl MazeMap {
+    /// Builds a `width x height` map of floor tiles, `tile_size` world units
+    /// per tile. Callers fill in walls via `map[y][x] = Tile::Wall(id)`.
+    pub fn new(width: usize, height: usize, tile_size: f32) -> Self {
+        MazeMap {
+            tile_size,
+            rows: vec![vec![Tile::Floor; width]; height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.rows.first().map_or(0, |row| row.len())
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &Vec<Tile>> {
+        self.rows.iter()
+    }
+
+    /// Whether the world-space point `(world_x, world_y)` falls on a wall
+    /// tile. Points outside the map entirely count as walls, so movement
+    /// can't walk off the edge of a non-square or undersized level.
+    pub fn is_wall_at(&self, world_x: f32, world_y: f32) -> bool {
+        let cell_x = world_x / self.tile_size;
+        let cell_y = world_y / self.tile_size;
+        if cell_x < 0.0 || cell_y < 0.0 {
+            return true;
+        }
+        let (cell_x, cell_y) = (cell_x as usize, cell_y as usize);
+        if cell_x >= self.width() || cell_y >= self.height() {
+            return true;
+        }
+        self[cell_y][cell_x].is_wall()
+    }
+}
+
+impl Index<usize> for MazeMap {
+    type Output = Vec<Tile>;
+
+    fn index(&self, y: usize) -> &Vec<Tile> {
+        &self.rows[y]
+    }
+}
+
+impl IndexMut<usize> for MazeMap {
+    fn index_mut(&mut self, y: usize) -> &mut Vec<Tile> {
+        &mut self.rows[y]
+    }
+}
+
+pub type SpawnPoints = Vec<(f32, f32)>;
+
+pub struct MazeLevel {
+    pub map: MazeMap,
+    pub spawns: SpawnPoints,
+}
+
+/// Builds a fresh `width x height` maze with the randomized depth-first
+/// "recursive backtracker": cells live on odd coordinates, one tile apart
+/// from their neighbors with a wall tile between, so carving a neighbor and
+/// the wall between it and the current cell in the same step always leaves
+/// a connected, cycle-free ("perfect") maze. `seed` drives every random
+/// choice through a `StdRng`, so the server and every client reproduce the
+/// exact same layout from the same seed. The outermost ring of tiles is
+/// never carved, so it stays a solid border wall.
+pub fn generate_procedural_maze(width: usize, height: usize, seed: u64) -> MazeLevel {
+    let mut map = MazeMap::new(width, height, 1.0);
+    for y in 0..height {
+        for x in 0..width {
+            map[y][x] = Tile::Wall(0);
+        }
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let start = (1usize, 1usize);
+    map[start.1][start.0] = Tile::Floor;
+    let mut stack = vec![start];
+
+    while let Some(&(cx, cy)) = stack.last() {
+        let neighbors: Vec<(usize, usize)> = [(0, -2), (0, 2), (-2, 0), (2, 0)]
+            .into_iter()
+            .filter_map(|(dx, dy): (isize, isize)| {
+                let nx = cx as isize + dx;
+                let ny = cy as isize + dy;
+                if nx > 0 && ny > 0 && (nx as usize) < width - 1 && (ny as usize) < height - 1 {
+                    Some((nx as usize, ny as usize))
+                } else {
+                    None
+                }
+            })
+            .filter(|&(nx, ny)| map[ny][nx].is_wall())
+            .collect();
+
+        if let Some(&(nx, ny)) = neighbors.choose(&mut rng) {
+            map[(cy + ny) / 2][(cx + nx) / 2] = Tile::Floor;
+            map[ny][nx] = Tile::Floor;
+            stack.push((nx, ny));
+        } else {
+            stack.pop();
+        }
+    }
+
+    let spawns = compute_spawns(&mut map, 10);
+
+    MazeLevel { map, spawns }
+}
+
+/// Picks `count` spawn points on `map`'s floor tiles, guaranteed reachable
+/// from each other and spread as far apart as possible.
+///
+/// Runs a BFS flood fill from an arbitrary floor tile to get every other
+/// floor tile's step distance; any floor tile the flood fill never reaches
+/// is an isolated pocket, so it's sealed into a wall - the rest of this
+/// function, and every caller, can then assume "floor" means "reachable".
+/// Spawns are then chosen by farthest-point sampling: start at the tile
+/// furthest (by BFS distance) from the flood-fill origin, then repeatedly
+/// add whichever remaining floor tile maximizes its minimum Manhattan
+/// distance to every spawn chosen so far.
+pub fn compute_spawns(map: &mut MazeMap, count: usize) -> SpawnPoints {
+    let width = map.width();
+    let height = map.height();
+
+    let origin = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .find(|&(x, y)| !map[y][x].is_wall());
+    let Some(origin) = origin else {
+        return Vec::new();
+    };
+
+    let mut dist = vec![vec![None; width]; height];
+    let mut queue = VecDeque::new();
+    dist[origin.1][origin.0] = Some(0u32);
+    queue.push_back(origin);
+    while let Some((x, y)) = queue.pop_front() {
+        let d = dist[y][x].unwrap();
+        for (nx, ny) in orthogonal_neighbors(x, y, width, height) {
+            if !map[ny][nx].is_wall() && dist[ny][nx].is_none() {
+                dist[ny][nx] = Some(d + 1);
+                queue.push_back((nx, ny));
+            }
+        }
+    }
+
+    for y in 0..height {
+        for x in 0..width {
+            if !map[y][x].is_wall() && dist[y][x].is_none() {
+                map[y][x] = Tile::Wall(0);
+            }
+        }
+    }
+
+    let reachable: Vec<(usize, usize)> = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .filter(|&(x, y)| dist[y][x].is_some())
+        .collect();
+    if reachable.is_empty() {
+        return Vec::new();
+    }
+
+    let mut spawns = vec![*reachable
+        .iter()
+        .max_by_key(|&&(x, y)| dist[y][x].unwrap())
+        .unwrap()];
+
+    while spawns.len() < count && spawns.len() < reachable.len() {
+        let next = reachable
+            .iter()
+            .filter(|cell| !spawns.contains(cell))
+            .max_by_key(|&&(x, y)| {
+                spawns
+                    .iter()
+                    .map(|&(sx, sy)| x.abs_diff(sx) + y.abs_diff(sy))
+                    .min()
+                    .unwrap_or(0)
+            })
+            .copied();
+        match next {
+            Some(cell) => spawns.push(cell),
+            None => break,
+        }
+    }
+
+    spawns
+        .into_iter()
+        .map(|(x, y)| (x as f32 + 0.5, y as f32 + 0.5))
+        .collect()
+}
+
+/// The up-to-four tile-adjacent (not diagonal) in-bounds neighbors of
+/// `(x, y)` in a `width x height` grid.
+fn orthogonal_neighbors(x: usize, y: usize, width: usize, height: usize) -> Vec<(usize, usize)> {
+    let mut neighbors = Vec::with_capacity(4);
+    if x > 0 {
+        neighbors.push((x - 1, y));
+    }
+    if x + 1 < width {
+        neighbors.push((x + 1, y));
+    }
+    if y > 0 {
+        neighbors.push((x, y - 1));
+    }
+    if y + 1 < height {
+        neighbors.push((x, y + 1));
+    }
+    neighbors
+}
\ No newline at end of file
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::time::Instant;
 
 use crate::{rotation::Rotation, weapon::Weapon, Position};
 
@@ -11,14 +12,28 @@ use crate::{rotation::Rotation, weapon::Weapon, Position};
 /// - `height`: Player's height in centimeters
 /// - `rotation`: Current orientation
 /// - `health`: Health points (0-100)
-/// - `weapon`: Equipped weapon stats
+/// - `inventory`: Carried weapons; `equipped` indexes the one currently in hand
+/// - `equipped`: Index into `inventory` of the currently equipped weapon
+/// - `pubkey`: Verified ed25519 public key proved during the connect handshake; this
+///   player's durable identity, independent of the UDP address packets arrive from
+/// - `last_seen`: Local timestamp of the last message received from this player (never
+///   serialized; reset to "now" on deserialization since it is only meaningful server-side)
+///
+/// Unlike `Position`/`Rotation`, this struct is additive-only: it's expected to
+/// grow new fields as the game does, so it deliberately has no
+/// `#[serde(deny_unknown_fields)]` - an older binary should ignore fields it
+/// doesn't recognize rather than fail to deserialize a snapshot entirely.
 pub struct Player {
     pub username: String,
     pub position: Position,
     pub height: u32,
     pub rotation: Rotation,
     pub health: u32,
-    pub weapon: Weapon,
+    pub inventory: Vec<Weapon>,
+    pub equipped: usize,
+    pub pubkey: [u8; 32],
+    #[serde(skip, default = "Instant::now")]
+    pub last_seen: Instant,
 }
 
 impl Player {
@@ -31,17 +46,19 @@ impl Player {
     /// - `position`: The initial position of the player
     /// - `rotation`: The initial rotation of the player
     /// - `health`: The initial health of the player
-    /// - `weapon`: The initial weapon of the player
+    /// - `inventory`: The player's starting loadout; must be non-empty
+    /// - `pubkey`: Verified ed25519 public key proved during the connect handshake
     ///
     /// # Returns
-    /// A new `Player` instance
+    /// A new `Player` instance, equipped with `inventory[0]`
     pub fn new(
         username: String,
         position: Position,
         height: u32,
         rotation: Rotation,
         health: u32,
-        weapon: Weapon,
+        inventory: Vec<Weapon>,
+        pubkey: [u8; 32],
     ) -> Self {
         Self {
             username,
@@ -49,7 +66,35 @@ impl Player {
             height,
             rotation,
             health,
-            weapon,
+            inventory,
+            equipped: 0,
+            pubkey,
+            last_seen: Instant::now(),
         }
     }
+
+    /// The currently equipped weapon.
+    ///
+    /// # Panics
+    /// Panics if `equipped` is out of bounds for `inventory`, which should
+    /// only happen if `inventory` was constructed empty.
+    pub fn weapon(&self) -> &Weapon {
+        &self.inventory[self.equipped]
+    }
+
+    /// Mutable access to the currently equipped weapon - used to decrement
+    /// `ammo_count` on a fired shot and refill it on a completed reload.
+    ///
+    /// # Panics
+    /// Panics if `equipped` is out of bounds for `inventory`, which should
+    /// only happen if `inventory` was constructed empty.
+    pub fn weapon_mut(&mut self) -> &mut Weapon {
+        &mut self.inventory[self.equipped]
+    }
+
+    /// Refreshes the liveness timestamp; call this from every handler that receives
+    /// a datagram from this player so the heartbeat reaper doesn't time them out.
+    pub fn touch(&mut self) {
+        self.last_seen = Instant::now();
+    }
 }
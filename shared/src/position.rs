@@ -2,7 +2,10 @@ use bevy::math::Vec3;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy)]
-/// Represents a 3D position in game world
+#[serde(deny_unknown_fields)]
+/// Represents a 3D position in game world. Part of the frozen wire protocol -
+/// `x`/`y`/`z` are the whole shape, so an unrecognized field on the wire means
+/// a client/server protocol mismatch rather than a forward-compatible addition.
 ///
 /// # Examples
 /// ```rust
@@ -29,6 +32,47 @@ impl Position {
     pub fn new(x: f32, y: f32, z: f32) -> Self {
         Self { x, y, z }
     }
+
+    /// This position offset by `distance` along `direction` - the movement-vector
+    /// pattern where a direction plus a step count yields the next coordinate.
+    /// `direction` is assumed already normalized, matching `Rotation::forward_vector`
+    /// and `ClientMessage::ShotPlayer`'s `direction` field.
+    pub fn offset(&self, direction: Position, distance: f32) -> Position {
+        Position::new(
+            self.x + direction.x * distance,
+            self.y + direction.y * distance,
+            self.z + direction.z * distance,
+        )
+    }
+
+    /// Straight-line distance to `other`.
+    pub fn distance(&self, other: &Position) -> f32 {
+        let dx = self.x - other.x;
+        let dy = self.y - other.y;
+        let dz = self.z - other.z;
+        (dx * dx + dy * dy + dz * dz).sqrt()
+    }
+
+    /// Euclidean length, for when this `Position` is being used as a
+    /// direction/offset vector rather than a point.
+    pub fn length(&self) -> f32 {
+        (self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
+    }
+
+    /// This vector rescaled to unit length, or `None` if it's too close to
+    /// the zero vector to have a meaningful direction. Several call sites
+    /// (`Position::offset`, raycasting in `server::raycast`) assume
+    /// whatever `Position` they're given as a direction is already a unit
+    /// vector - use this to make that true for client-supplied input instead
+    /// of trusting the claim.
+    pub fn normalized(&self) -> Option<Position> {
+        let len = self.length();
+        if len < f32::EPSILON {
+            None
+        } else {
+            Some(Position::new(self.x / len, self.y / len, self.z / len))
+        }
+    }
 }
 
 impl Default for Position {
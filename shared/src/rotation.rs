@@ -1,8 +1,14 @@
 use bevy::math::{EulerRot, Quat};
 use serde::{Deserialize, Serialize};
 
+use crate::Position;
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy)]
-/// Represents player's rotation/orientation in 3D space
+#[serde(deny_unknown_fields)]
+/// Represents player's rotation/orientation in 3D space. Part of the frozen
+/// wire protocol - `pitch`/`yaw`/`roll` are the whole shape, so an unrecognized
+/// field on the wire means a client/server protocol mismatch rather than a
+/// forward-compatible addition.
 ///
 /// # Examples
 /// ```rust
@@ -19,6 +25,21 @@ impl Rotation {
     pub fn new(pitch: f32, yaw: f32, roll: f32) -> Self {
         Self { pitch, yaw, roll }
     }
+
+    /// Unit vector this rotation faces, in standard spherical-to-cartesian
+    /// form: `yaw` turns around the vertical axis and `pitch` tilts up/down
+    /// out of the x/y plane the maze's geometry is confined to, so scaling
+    /// the x/y components by `cos(pitch)` keeps the whole vector's magnitude
+    /// at 1 regardless of pitch. Combined with `Position::offset` and
+    /// `Position::distance`, this is what a server-side hit check ray-marches
+    /// along: `shooter_position.offset(rotation.forward_vector(), step)`.
+    pub fn forward_vector(&self) -> Position {
+        Position::new(
+            self.pitch.cos() * self.yaw.cos(),
+            self.pitch.cos() * self.yaw.sin(),
+            self.pitch.sin(),
+        )
+    }
 }
 
 impl Default for Rotation {
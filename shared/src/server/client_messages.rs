@@ -1,19 +1,174 @@
 use serde::{Deserialize, Serialize};
 
+use crate::codec::{
+    read_array, read_f32, read_position, read_rotation, read_string, read_u32, read_u64,
+    write_f32, write_position, write_rotation, write_string, write_u32, write_u64,
+};
 use crate::{rotation::Rotation, Position};
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "type", content = "data")]
 pub enum ClientMessage {
+    /// The very first message a client sends after opening its socket, before
+    /// `JoinGame` - lets the server reject an incompatible client with
+    /// `ServerMessage::JoinGameError`. The bundled client sends `JoinGame`
+    /// straight after it, without waiting for a reply.
+    Hello { protocol_version: u32 },
     JoinGame {
         username: String,
+        protocol_version: u32,
+    },
+    /// Completes the handshake started by `JoinGame`: proves ownership of `pubkey`
+    /// by signing the nonce the server sent back in `ServerMessage::Challenge`.
+    /// `pubkey` becomes this player's durable identity, independent of the UDP
+    /// address the datagram arrived on.
+    Connect {
+        pubkey: [u8; 32],
+        nonce: [u8; 32],
+        signature: [u8; 64],
     },
     Move {
         position: Position,
         rotation: Rotation,
         yield_control: f32,
+        /// Sequence number of the local input that produced this move, echoed back
+        /// in `ServerMessage::WorldFrame::last_processed_input` so the client knows
+        /// which of its predicted inputs the server has already applied.
+        input_sequence: u32,
     },
+    /// A fired shot. Rather than naming a target, the client reports where the
+    /// shot came from and where it's aimed; the server raycasts against the maze
+    /// and every other player itself to decide authoritatively who, if anyone, was hit.
     ShotPlayer {
-        player_username: String,
+        origin: Position,
+        /// Normalized aim direction.
+        direction: Position,
     },
+    /// Sent periodically so the server can tell a connected player apart from one that
+    /// crashed or walked away; refreshes that player's `last_seen` timestamp.
+    Heartbeat,
+    /// Round-trip latency probe; `client_time` is opaque to the server and simply
+    /// echoed back in `ServerMessage::Pong` for the client to diff against its own clock.
+    Ping { client_time: u64 },
+    /// Equips the weapon at `index` in the player's inventory. Rejected
+    /// silently if `index` is out of range.
+    SwitchWeapon { index: u8 },
+    /// Begins reloading the currently equipped weapon. The server refills its
+    /// `ammo_count` back to `magazine_size` after that weapon's `reload_time`
+    /// has elapsed and announces it with `ServerMessage::ReloadComplete`.
+    Reload,
+}
+
+impl ClientMessage {
+    /// Whether this variant must be delivered on the reliable channel - sequenced
+    /// and resent on a backoff until acked - instead of the unreliable one.
+    /// `Move`, `Heartbeat` and `Ping` are sent at a high, regular rate, so a dropped
+    /// one is harmless: the next send supersedes it.
+    pub fn is_reliable(&self) -> bool {
+        !matches!(
+            self,
+            ClientMessage::Move { .. } | ClientMessage::Heartbeat | ClientMessage::Ping { .. }
+        )
+    }
+
+    /// Packs this message as a leading variant tag byte followed by its fields,
+    /// quantizing `Position`/`Rotation` instead of shipping raw `f32`s. Far
+    /// smaller than `bincode::serialize`. The bundled client and server do not
+    /// call it: they send every message, `Move` included, with `bincode`.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        match self {
+            ClientMessage::Hello { protocol_version } => {
+                out.push(6);
+                write_u32(*protocol_version, &mut out);
+            }
+            ClientMessage::JoinGame {
+                username,
+                protocol_version,
+            } => {
+                out.push(0);
+                write_string(username, &mut out);
+                write_u32(*protocol_version, &mut out);
+            }
+            ClientMessage::Connect {
+                pubkey,
+                nonce,
+                signature,
+            } => {
+                out.push(1);
+                out.extend_from_slice(pubkey);
+                out.extend_from_slice(nonce);
+                out.extend_from_slice(signature);
+            }
+            ClientMessage::Move {
+                position,
+                rotation,
+                yield_control,
+                input_sequence,
+            } => {
+                out.push(2);
+                write_position(position, &mut out);
+                write_rotation(rotation, &mut out);
+                write_f32(*yield_control, &mut out);
+                write_u32(*input_sequence, &mut out);
+            }
+            ClientMessage::ShotPlayer { origin, direction } => {
+                out.push(3);
+                write_position(origin, &mut out);
+                write_position(direction, &mut out);
+            }
+            ClientMessage::Heartbeat => out.push(4),
+            ClientMessage::Ping { client_time } => {
+                out.push(5);
+                write_u64(*client_time, &mut out);
+            }
+            ClientMessage::SwitchWeapon { index } => {
+                out.push(7);
+                out.push(*index);
+            }
+            ClientMessage::Reload => out.push(8),
+        }
+        out
+    }
+
+    /// Reverses `encode`. `None` if `bytes` names an unknown tag or ends before
+    /// a variant's fields are fully read.
+    pub fn decode(bytes: &[u8]) -> Option<Self> {
+        let mut pos = 0;
+        let tag = *bytes.first()?;
+        pos += 1;
+        match tag {
+            0 => Some(ClientMessage::JoinGame {
+                username: read_string(bytes, &mut pos)?,
+                protocol_version: read_u32(bytes, &mut pos)?,
+            }),
+            1 => Some(ClientMessage::Connect {
+                pubkey: read_array::<32>(bytes, &mut pos)?,
+                nonce: read_array::<32>(bytes, &mut pos)?,
+                signature: read_array::<64>(bytes, &mut pos)?,
+            }),
+            2 => Some(ClientMessage::Move {
+                position: read_position(bytes, &mut pos)?,
+                rotation: read_rotation(bytes, &mut pos)?,
+                yield_control: read_f32(bytes, &mut pos)?,
+                input_sequence: read_u32(bytes, &mut pos)?,
+            }),
+            3 => Some(ClientMessage::ShotPlayer {
+                origin: read_position(bytes, &mut pos)?,
+                direction: read_position(bytes, &mut pos)?,
+            }),
+            4 => Some(ClientMessage::Heartbeat),
+            5 => Some(ClientMessage::Ping {
+                client_time: read_u64(bytes, &mut pos)?,
+            }),
+            6 => Some(ClientMessage::Hello {
+                protocol_version: read_u32(bytes, &mut pos)?,
+            }),
+            7 => Some(ClientMessage::SwitchWeapon {
+                index: *bytes.get(pos)?,
+            }),
+            8 => Some(ClientMessage::Reload),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,14 @@
+mod client_messages;
+mod server_messages;
+
+pub use client_messages::ClientMessage;
+pub use server_messages::ServerMessage;
+
+/// Current wire protocol version. Bump this whenever a `ClientMessage`/`ServerMessage`
+/// change isn't backwards compatible; `handle_hello` and `handle_join_game` both refuse
+/// a mismatched client instead of risking a silent "Bad Payload" desync.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Compact numeric player identifier handed out by the server's slot allocator,
+/// used on the wire instead of usernames to keep broadcasts small.
+pub type PlayerId = u8;
@@ -1,6 +1,13 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
-use crate::{rotation::Rotation, Position};
+use crate::codec::{
+    capped_len, read_array, read_f32, read_player, read_position, read_rotation, read_string,
+    read_u32, read_u64, read_varint, write_f32, write_player, write_position, write_rotation,
+    write_string, write_u32, write_u64, write_varint,
+};
+use crate::{rotation::Rotation, server::PlayerId, Player, Position};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(tag = "type", content = "data")]
@@ -11,33 +18,387 @@ pub enum ServerMessage {
     JoinGameError {
         message: String,
     },
+    /// Sent once, immediately after a successful join, so the client can confirm it's
+    /// speaking a compatible protocol version and learn its assigned numeric id
+    Meta {
+        protocol_version: u32,
+        server_name: String,
+        player_id: PlayerId,
+    },
+    /// Reply to `ClientMessage::JoinGame`: a one-time nonce the client must sign with
+    /// its ed25519 key and echo back in `ClientMessage::Connect` to prove ownership
+    /// of the pubkey it's claiming, and to stop a captured `Connect` from being replayed.
+    Challenge {
+        nonce: [u8; 32],
+    },
     PlayersInLobby {
         player_count: u32,
         players: Vec<String>,
     },
     GameStart {
-        maze_level: u8, 
+        maze_level: u8,
     },
     PlayerMove {
-        player_id: String,
+        player_id: PlayerId,
         position: Position,
         rotation: Rotation,
         yield_control: f32,
     },
 
     PlayerDeath {
-        player_id: String,
-        killer_id: Option<String>,
+        player_id: PlayerId,
+        killer_id: Option<PlayerId>,
     },
     PlayerSpawn {
-        player_id: String,
+        player_id: PlayerId,
         position: Position,
     },
     HealthUpdate {
-        player_id: String,
+        player_id: PlayerId,
         health: u32,
     },
     GameOver {
         winner: String,
     },
+    /// Broadcast when the heartbeat reaper drops a player - either for going quiet
+    /// past the configured `heartbeat_timeout`, or for falling too far behind on
+    /// reliable acks - so remaining clients can despawn that avatar.
+    PlayerDisconnected {
+        player_id: PlayerId,
+    },
+    /// Reply to `ClientMessage::Ping`, echoing `client_time` back unmodified so the
+    /// client can diff it against its own clock to estimate round-trip latency.
+    Pong {
+        client_time: u64,
+    },
+    /// Authoritative world snapshot broadcast on every server tick: every
+    /// player's position/rotation, plus in `last_processed_input` the highest
+    /// `Move` input sequence applied per player. The bundled client ignores
+    /// it; it does no prediction or reconciliation.
+    WorldFrame {
+        tick: u32,
+        last_processed_input: HashMap<PlayerId, u32>,
+        players: Vec<(PlayerId, Position, Rotation)>,
+    },
+    /// Sent once to a client that connects while a match is already in progress,
+    /// so it can render every other player at their last known state immediately
+    /// instead of waiting on each one's next `PlayerMove`. Mirrors the server's
+    /// own `GameSnapshot` save/restore format, minus the match metadata that's
+    /// only meaningful server-side.
+    GameSnapshot {
+        players: Vec<Player>,
+        tick: u64,
+    },
+    /// Broadcast whenever a player's `ClientMessage::SwitchWeapon` is accepted,
+    /// so every client's view of that player's held weapon stays in sync.
+    WeaponSwitch {
+        player_id: PlayerId,
+        index: u8,
+    },
+    /// Broadcast when a `ClientMessage::Reload` finishes, carrying the refilled
+    /// `ammo_count` so clients don't have to assume it matches `magazine_size`.
+    ReloadComplete {
+        player_id: PlayerId,
+        ammo_count: u32,
+    },
+}
+
+impl ServerMessage {
+    /// Whether this variant must be delivered on the reliable channel - sequenced
+    /// and resent on a backoff until acked - instead of the unreliable one.
+    /// `PlayerMove`, `WorldFrame` and `Pong` are the exceptions: all three are sent
+    /// at a high rate (or are only useful fresh), so a dropped one is immediately
+    /// superseded by the next, and neither is worth the resend/ack overhead.
+    pub fn is_reliable(&self) -> bool {
+        !matches!(
+            self,
+            ServerMessage::PlayerMove { .. }
+                | ServerMessage::WorldFrame { .. }
+                | ServerMessage::Pong { .. }
+        )
+    }
+
+    /// Packs this message as a leading variant tag byte followed by its fields,
+    /// quantizing `Position`/`Rotation` instead of shipping raw `f32`s. Far
+    /// smaller than `bincode::serialize`. The bundled client and server do not
+    /// call it: they send every message, `PlayerMove`/`WorldFrame` included,
+    /// with `bincode`.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        match self {
+            ServerMessage::Error { message } => {
+                out.push(0);
+                write_string(message, &mut out);
+            }
+            ServerMessage::JoinGameError { message } => {
+                out.push(1);
+                write_string(message, &mut out);
+            }
+            ServerMessage::Meta {
+                protocol_version,
+                server_name,
+                player_id,
+            } => {
+                out.push(2);
+                write_u32(*protocol_version, &mut out);
+                write_string(server_name, &mut out);
+                out.push(*player_id);
+            }
+            ServerMessage::Challenge { nonce } => {
+                out.push(3);
+                out.extend_from_slice(nonce);
+            }
+            ServerMessage::PlayersInLobby {
+                player_count,
+                players,
+            } => {
+                out.push(4);
+                write_u32(*player_count, &mut out);
+                write_varint(players.len() as u32, &mut out);
+                for player in players {
+                    write_string(player, &mut out);
+                }
+            }
+            ServerMessage::GameStart { maze_level } => {
+                out.push(5);
+                out.push(*maze_level);
+            }
+            ServerMessage::PlayerMove {
+                player_id,
+                position,
+                rotation,
+                yield_control,
+            } => {
+                out.push(6);
+                out.push(*player_id);
+                write_position(position, &mut out);
+                write_rotation(rotation, &mut out);
+                write_f32(*yield_control, &mut out);
+            }
+            ServerMessage::PlayerDeath {
+                player_id,
+                killer_id,
+            } => {
+                out.push(7);
+                out.push(*player_id);
+                match killer_id {
+                    Some(id) => {
+                        out.push(1);
+                        out.push(*id);
+                    }
+                    None => out.push(0),
+                }
+            }
+            ServerMessage::PlayerSpawn {
+                player_id,
+                position,
+            } => {
+                out.push(8);
+                out.push(*player_id);
+                write_position(position, &mut out);
+            }
+            ServerMessage::HealthUpdate { player_id, health } => {
+                out.push(9);
+                out.push(*player_id);
+                write_u32(*health, &mut out);
+            }
+            ServerMessage::GameOver { winner } => {
+                out.push(10);
+                write_string(winner, &mut out);
+            }
+            ServerMessage::PlayerDisconnected { player_id } => {
+                out.push(11);
+                out.push(*player_id);
+            }
+            ServerMessage::Pong { client_time } => {
+                out.push(12);
+                write_u64(*client_time, &mut out);
+            }
+            ServerMessage::WorldFrame {
+                tick,
+                last_processed_input,
+                players,
+            } => {
+                out.push(13);
+                write_u32(*tick, &mut out);
+                write_varint(last_processed_input.len() as u32, &mut out);
+                for (player_id, input_sequence) in last_processed_input {
+                    out.push(*player_id);
+                    write_u32(*input_sequence, &mut out);
+                }
+                write_varint(players.len() as u32, &mut out);
+                for (player_id, position, rotation) in players {
+                    out.push(*player_id);
+                    write_position(position, &mut out);
+                    write_rotation(rotation, &mut out);
+                }
+            }
+            ServerMessage::GameSnapshot { players, tick } => {
+                out.push(14);
+                write_varint(players.len() as u32, &mut out);
+                for player in players {
+                    write_player(player, &mut out);
+                }
+                write_u64(*tick, &mut out);
+            }
+            ServerMessage::WeaponSwitch { player_id, index } => {
+                out.push(15);
+                out.push(*player_id);
+                out.push(*index);
+            }
+            ServerMessage::ReloadComplete {
+                player_id,
+                ammo_count,
+            } => {
+                out.push(16);
+                out.push(*player_id);
+                write_u32(*ammo_count, &mut out);
+            }
+        }
+        out
+    }
+
+    /// Reverses `encode`. `None` if `bytes` names an unknown tag or ends before
+    /// a variant's fields are fully read.
+    pub fn decode(bytes: &[u8]) -> Option<Self> {
+        let mut pos = 0;
+        let tag = *bytes.first()?;
+        pos += 1;
+        match tag {
+            0 => Some(ServerMessage::Error {
+                message: read_string(bytes, &mut pos)?,
+            }),
+            1 => Some(ServerMessage::JoinGameError {
+                message: read_string(bytes, &mut pos)?,
+            }),
+            2 => Some(ServerMessage::Meta {
+                protocol_version: read_u32(bytes, &mut pos)?,
+                server_name: read_string(bytes, &mut pos)?,
+                player_id: *bytes.get(pos)?,
+            }),
+            3 => Some(ServerMessage::Challenge {
+                nonce: read_array::<32>(bytes, &mut pos)?,
+            }),
+            4 => {
+                let player_count = read_u32(bytes, &mut pos)?;
+                let count = read_varint(bytes, &mut pos)?;
+                let mut players = Vec::with_capacity(capped_len(count, bytes, pos));
+                for _ in 0..count {
+                    players.push(read_string(bytes, &mut pos)?);
+                }
+                Some(ServerMessage::PlayersInLobby {
+                    player_count,
+                    players,
+                })
+            }
+            5 => Some(ServerMessage::GameStart {
+                maze_level: *bytes.get(pos)?,
+            }),
+            6 => {
+                let player_id = *bytes.get(pos)?;
+                pos += 1;
+                Some(ServerMessage::PlayerMove {
+                    player_id,
+                    position: read_position(bytes, &mut pos)?,
+                    rotation: read_rotation(bytes, &mut pos)?,
+                    yield_control: read_f32(bytes, &mut pos)?,
+                })
+            }
+            7 => {
+                let player_id = *bytes.get(pos)?;
+                pos += 1;
+                let has_killer = *bytes.get(pos)?;
+                pos += 1;
+                let killer_id = if has_killer == 1 {
+                    let id = *bytes.get(pos)?;
+                    pos += 1;
+                    Some(id)
+                } else {
+                    None
+                };
+                Some(ServerMessage::PlayerDeath {
+                    player_id,
+                    killer_id,
+                })
+            }
+            8 => {
+                let player_id = *bytes.get(pos)?;
+                pos += 1;
+                Some(ServerMessage::PlayerSpawn {
+                    player_id,
+                    position: read_position(bytes, &mut pos)?,
+                })
+            }
+            9 => {
+                let player_id = *bytes.get(pos)?;
+                pos += 1;
+                Some(ServerMessage::HealthUpdate {
+                    player_id,
+                    health: read_u32(bytes, &mut pos)?,
+                })
+            }
+            10 => Some(ServerMessage::GameOver {
+                winner: read_string(bytes, &mut pos)?,
+            }),
+            11 => Some(ServerMessage::PlayerDisconnected {
+                player_id: *bytes.get(pos)?,
+            }),
+            12 => Some(ServerMessage::Pong {
+                client_time: read_u64(bytes, &mut pos)?,
+            }),
+            13 => {
+                let tick = read_u32(bytes, &mut pos)?;
+                let input_count = read_varint(bytes, &mut pos)?;
+                let mut last_processed_input =
+                    HashMap::with_capacity(capped_len(input_count, bytes, pos));
+                for _ in 0..input_count {
+                    let player_id = *bytes.get(pos)?;
+                    pos += 1;
+                    last_processed_input.insert(player_id, read_u32(bytes, &mut pos)?);
+                }
+                let player_count = read_varint(bytes, &mut pos)?;
+                let mut players = Vec::with_capacity(capped_len(player_count, bytes, pos));
+                for _ in 0..player_count {
+                    let player_id = *bytes.get(pos)?;
+                    pos += 1;
+                    let position = read_position(bytes, &mut pos)?;
+                    let rotation = read_rotation(bytes, &mut pos)?;
+                    players.push((player_id, position, rotation));
+                }
+                Some(ServerMessage::WorldFrame {
+                    tick,
+                    last_processed_input,
+                    players,
+                })
+            }
+            14 => {
+                let count = read_varint(bytes, &mut pos)?;
+                let mut players = Vec::with_capacity(capped_len(count, bytes, pos));
+                for _ in 0..count {
+                    players.push(read_player(bytes, &mut pos)?);
+                }
+                Some(ServerMessage::GameSnapshot {
+                    players,
+                    tick: read_u64(bytes, &mut pos)?,
+                })
+            }
+            15 => {
+                let player_id = *bytes.get(pos)?;
+                pos += 1;
+                Some(ServerMessage::WeaponSwitch {
+                    player_id,
+                    index: *bytes.get(pos)?,
+                })
+            }
+            16 => {
+                let player_id = *bytes.get(pos)?;
+                pos += 1;
+                Some(ServerMessage::ReloadComplete {
+                    player_id,
+                    ammo_count: read_u32(bytes, &mut pos)?,
+                })
+            }
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,53 @@
+use std::fmt;
+
+/// Opaque peer identifier used by a [`Transport`] impl. Wraps the underlying
+/// address representation (a `SocketAddr` for the UDP transport, a node id for
+/// the QUIC/iroh-net transport) behind a single string so upper layers don't
+/// need to know which backend is in use.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PeerId(pub String);
+
+impl fmt::Display for PeerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Per-message delivery semantics a [`Transport`] is asked to honor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryGuarantee {
+    /// Ordered and retransmitted until acknowledged (handshake, `GameStart`, `PlayerDeath`, ...)
+    Reliable,
+    /// Best-effort, no retransmission (high-frequency `Move` updates)
+    Unreliable,
+}
+
+/// Abstraction over how bytes move between server and clients, so the game
+/// logic doesn't care whether it's talking over raw UDP or a NAT-traversed
+/// QUIC/iroh-net connection.
+#[async_trait::async_trait]
+pub trait Transport: Send + Sync {
+    /// Sends `bytes` to a single peer with the requested delivery guarantee.
+    async fn send(
+        &self,
+        peer: &PeerId,
+        bytes: &[u8],
+        guarantee: DeliveryGuarantee,
+    ) -> std::io::Result<()>;
+
+    /// Waits for the next inbound message and the peer it came from.
+    async fn recv(&self) -> std::io::Result<(Vec<u8>, PeerId)>;
+
+    /// Sends `bytes` to every peer in `peers` with the requested delivery guarantee.
+    async fn broadcast(
+        &self,
+        peers: &[PeerId],
+        bytes: &[u8],
+        guarantee: DeliveryGuarantee,
+    ) -> std::io::Result<()> {
+        for peer in peers {
+            self.send(peer, bytes, guarantee).await?;
+        }
+        Ok(())
+    }
+}
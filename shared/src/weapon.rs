@@ -12,7 +12,16 @@ pub struct Weapon {
     pub damage: u32,
     pub fire_rate: f32,
     pub ammo_count: u32,
+    /// Ammo a full reload restores `ammo_count` to; `ammo_count` itself only ever
+    /// falls from here as shots are fired.
+    pub magazine_size: u32,
     pub range: f32,
+    /// Seconds a `ClientMessage::Reload` takes to refill `ammo_count` back to
+    /// `magazine_size`.
+    pub reload_time: f32,
+    /// Carried weight, used by `speed_multiplier` to slow a player down the
+    /// heavier their equipped weapon is.
+    pub weight: f32,
 }
 
 impl Weapon {
@@ -23,29 +32,45 @@ impl Weapon {
             damage: 25,
             fire_rate: 1.5,
             ammo_count: 12,
+            magazine_size: 12,
             range: 30.0,
+            reload_time: 1.5,
+            weight: 1.0,
         }
     }
 
-    // /// Returns a predefined rifle weapon
-    // pub fn rifle() -> Self {
-    //     Weapon {
-    //         name: String::from("Rifle"),
-    //         damage: 40,
-    //         fire_rate: 3.0,
-    //         ammo_count: 30,
-    //         range: 60.0,
-    //     }
-    // }
+    /// Returns a predefined rifle weapon
+    pub fn rifle() -> Self {
+        Weapon {
+            name: String::from("Rifle"),
+            damage: 40,
+            fire_rate: 3.0,
+            ammo_count: 30,
+            magazine_size: 30,
+            range: 60.0,
+            reload_time: 2.5,
+            weight: 2.5,
+        }
+    }
+
+    /// Returns a predefined sniper weapon
+    pub fn sniper() -> Self {
+        Weapon {
+            name: String::from("Sniper"),
+            damage: 90,
+            fire_rate: 0.8,
+            ammo_count: 5,
+            magazine_size: 5,
+            range: 100.0,
+            reload_time: 3.0,
+            weight: 3.0,
+        }
+    }
 
-    // /// Returns a predefined sniper weapon
-    // pub fn sniper() -> Self {
-    //     Weapon {
-    //         name: String::from("Sniper"),
-    //         damage: 90,
-    //         fire_rate: 0.8,
-    //         ammo_count: 5,
-    //         range: 100.0,
-    //     }
-    // }
+    /// Fraction of a player's base move speed they keep while this weapon is
+    /// equipped - heavier weapons slow movement down, the same way an item's
+    /// weight imposes an initiative penalty in a roguelike inventory system.
+    pub fn speed_multiplier(&self) -> f32 {
+        1.0 / (1.0 + self.weight * 0.1)
+    }
 }